@@ -0,0 +1,338 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use bevy_ecs::{entity::Entity, system::Resource};
+use bevy_input::gamepad::GamepadAxis;
+use bevy_utils::{HashMap, HashSet};
+use crossbeam::channel::Sender;
+
+use crate::{
+    GamepadId, error::GamepadError, platform::GamepadPlatformEvent, profile::GamepadChange,
+};
+
+/// Sending half of the channel from a platform driver to the plugin.
+///
+/// Sending never blocks the driver callback. The channel itself is unbounded: its capacity is a soft cap
+/// on the input changes waiting to be received, not a bounded channel. An input change sent while the cap
+/// is reached is dropped and counted in [`GamepadChannelStats::dropped`]. Connections, disconnections and
+/// errors are never dropped nor counted against the cap, so the plugin always knows which devices are
+/// connected.
+#[derive(Debug, Clone)]
+pub struct EventSender {
+    tx: Sender<GamepadPlatformEvent>,
+    capacity: Option<usize>,
+    pending: Arc<AtomicUsize>,
+    dropped: Arc<AtomicUsize>,
+    unhandled: Arc<AtomicUsize>,
+}
+
+impl EventSender {
+    pub(crate) fn new(
+        tx: Sender<GamepadPlatformEvent>,
+        capacity: Option<usize>,
+        pending: Arc<AtomicUsize>,
+        dropped: Arc<AtomicUsize>,
+        unhandled: Arc<AtomicUsize>,
+    ) -> Self {
+        Self {
+            tx,
+            capacity,
+            pending,
+            dropped,
+            unhandled,
+        }
+    }
+
+    /// Send an event to the plugin. Only fails if the plugin has been dropped.
+    pub fn send(&self, event: GamepadPlatformEvent) -> Result<(), GamepadError> {
+        let input = matches!(event, GamepadPlatformEvent::InputChanged { .. });
+        if input {
+            let pending = self.pending.fetch_add(1, Ordering::Relaxed);
+            if self.capacity.is_some_and(|capacity| pending >= capacity) {
+                self.pending.fetch_sub(1, Ordering::Relaxed);
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                return Ok(());
+            }
+        }
+
+        self.tx.send(event).map_err(|_| {
+            if input {
+                self.pending.fetch_sub(1, Ordering::Relaxed);
+            }
+            GamepadError::ChannelClosed
+        })
+    }
+
+    /// Count a change of an element the profile of the device has no mapping for, shown in
+    /// [`GamepadChannelStats::unhandled`]
+    pub fn unhandled(&self) {
+        self.unhandled.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Counters of the channel from the platform driver, updated every frame
+#[derive(Resource, Debug, Default, Clone)]
+pub struct GamepadChannelStats {
+    received: usize,
+    coalesced: usize,
+    dropped: usize,
+    queued: usize,
+    orphaned: usize,
+    unhandled: usize,
+    frame_received: usize,
+    depth: usize,
+    latency: Option<Duration>,
+    frame_events: HashMap<Entity, usize>,
+}
+
+impl GamepadChannelStats {
+    /// Events received from the platform driver since the plugin was built
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    /// Axis changes discarded because a later value of the same axis arrived in the same frame
+    pub fn coalesced(&self) -> usize {
+        self.coalesced
+    }
+
+    /// Input events dropped by the platform driver because the channel capacity was reached
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Events left in the channel for the next frame
+    pub fn queued(&self) -> usize {
+        self.queued
+    }
+
+    /// Input events discarded because their device did not connect within
+    /// [`crate::GamepadPluginSettings::orphan_timeout`], or was disconnected
+    pub fn orphaned(&self) -> usize {
+        self.orphaned
+    }
+
+    /// Element changes the profiles of the devices have no mapping for
+    pub fn unhandled(&self) -> usize {
+        self.unhandled
+    }
+
+    /// Events received from the platform driver in the last frame
+    pub fn frame_received(&self) -> usize {
+        self.frame_received
+    }
+
+    /// Events in the channel when it was drained in the last frame
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Longest time from a driver callback to the platform system of the input events received in
+    /// the last frame, or `None` if no input was received
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
+
+    /// Input events of each gamepad entity processed in the last frame
+    pub fn frame_events(&self) -> impl Iterator<Item = (Entity, usize)> + '_ {
+        self.frame_events
+            .iter()
+            .map(|(gamepad, count)| (*gamepad, *count))
+    }
+
+    pub(crate) fn record_frame(
+        &mut self,
+        received: usize,
+        coalesced: usize,
+        depth: usize,
+        queued: usize,
+        latency: Option<Duration>,
+    ) {
+        self.received += received;
+        self.coalesced += coalesced;
+        self.frame_received = received;
+        self.depth = depth;
+        self.queued = queued;
+        self.latency = latency;
+        self.frame_events.clear();
+    }
+
+    pub(crate) fn record_event(&mut self, gamepad: Entity) {
+        *self.frame_events.entry(gamepad).or_default() += 1;
+    }
+
+    pub(crate) fn update_unhandled(&mut self, unhandled: usize) {
+        self.unhandled = unhandled;
+    }
+
+    pub(crate) fn record_orphaned(&mut self, orphaned: usize) {
+        self.orphaned += orphaned;
+    }
+
+    /// Update the dropped count, returning the number of events dropped since the last update
+    pub(crate) fn update_dropped(&mut self, dropped: usize) -> usize {
+        let new = dropped.saturating_sub(self.dropped);
+        self.dropped = dropped;
+        new
+    }
+}
+
+/// Remove axis changes superseded by a later change of the same axis of the same device, keeping the order
+/// of the remaining events. Button changes are never removed, so every press and release is preserved.
+/// Returns the number of events removed.
+pub(crate) fn coalesce_axes(events: &mut Vec<GamepadPlatformEvent>) -> usize {
+    let mut later = HashSet::<(GamepadId, GamepadAxis)>::default();
+    let mut keep = vec![true; events.len()];
+
+    for (index, event) in events.iter().enumerate().rev() {
+        match event {
+            GamepadPlatformEvent::InputChanged {
+                id,
+                change: GamepadChange::DualAxis { x_axis, y_axis, .. },
+                ..
+            } => {
+                // Only superseded when both axes have a later value
+                let x_latest = later.insert((*id, *x_axis));
+                let y_latest = later.insert((*id, *y_axis));
+                keep[index] = x_latest || y_latest;
+            }
+
+            GamepadPlatformEvent::InputChanged {
+                id,
+                change: GamepadChange::SingleAxis { axis, .. },
+                ..
+            } => {
+                keep[index] = later.insert((*id, *axis));
+            }
+
+            // Values do not carry over a reconnection of the device
+            GamepadPlatformEvent::Connected { id, .. }
+            | GamepadPlatformEvent::Disconnected { id } => {
+                later.retain(|(other, _)| other != id);
+            }
+
+            _ => {}
+        }
+    }
+
+    let count = events.len();
+    let mut keep = keep.into_iter();
+    events.retain(|_| keep.next().unwrap_or(true));
+    count - events.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_input::gamepad::{GamepadButton, GamepadConnection};
+
+    use super::*;
+    use crate::{
+        device::GamepadDeviceInfo,
+        glyph::ButtonLabels,
+        profile::{ButtonChange, FaceLayout},
+        timestamp::InputTimestamp,
+    };
+
+    fn input(id: GamepadId, change: GamepadChange) -> GamepadPlatformEvent {
+        GamepadPlatformEvent::InputChanged {
+            id,
+            change,
+            timestamp: InputTimestamp::now(),
+        }
+    }
+
+    fn axis(id: GamepadId, axis: GamepadAxis, value: f32) -> GamepadPlatformEvent {
+        input(id, GamepadChange::SingleAxis { axis, value })
+    }
+
+    fn stick(id: GamepadId, x_value: f32, y_value: f32) -> GamepadPlatformEvent {
+        input(
+            id,
+            GamepadChange::DualAxis {
+                x_axis: GamepadAxis::LeftStickX,
+                x_value,
+                y_axis: GamepadAxis::LeftStickY,
+                y_value,
+            },
+        )
+    }
+
+    fn button(id: GamepadId, value: f32) -> GamepadPlatformEvent {
+        input(
+            id,
+            GamepadChange::Button(ButtonChange::new(GamepadButton::South, value)),
+        )
+    }
+
+    /// Changes of the events left after coalescing, to compare them without timestamps
+    fn changes(events: &[GamepadPlatformEvent]) -> Vec<(GamepadId, GamepadChange)> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                GamepadPlatformEvent::InputChanged { id, change, .. } => {
+                    Some((*id, change.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn latest_axis_value_per_device() {
+        let mut events = vec![
+            axis(0, GamepadAxis::RightZ, 0.1),
+            axis(1, GamepadAxis::RightZ, 0.2),
+            axis(0, GamepadAxis::RightZ, 0.3),
+            axis(0, GamepadAxis::LeftZ, 0.4),
+        ];
+        let expected = changes(&events[1..]);
+
+        assert_eq!(coalesce_axes(&mut events), 1);
+        assert_eq!(changes(&events), expected);
+    }
+
+    #[test]
+    fn buttons_are_kept() {
+        let mut events = vec![button(0, 1.0), button(0, 0.0), button(0, 1.0)];
+        assert_eq!(coalesce_axes(&mut events), 0);
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn dual_axis_needs_both_axes_superseded() {
+        // The Y value of the first stick change is still the latest
+        let mut events = vec![stick(0, 0.1, 0.1), axis(0, GamepadAxis::LeftStickX, 0.2)];
+        assert_eq!(coalesce_axes(&mut events), 0);
+
+        events.push(stick(0, 0.3, 0.3));
+        let expected = changes(&events[2..]);
+        assert_eq!(coalesce_axes(&mut events), 2);
+        assert_eq!(changes(&events), expected);
+    }
+
+    #[test]
+    fn values_do_not_carry_over_reconnection() {
+        let mut events = vec![
+            axis(0, GamepadAxis::LeftZ, 0.5),
+            GamepadPlatformEvent::Disconnected { id: 0 },
+            GamepadPlatformEvent::Connected {
+                id: 0,
+                connection: GamepadConnection::Connected {
+                    name: String::from("Virtual"),
+                    vendor_id: None,
+                    product_id: None,
+                },
+                info: GamepadDeviceInfo::new("Virtual", FaceLayout::Standard),
+                labels: ButtonLabels::GENERIC,
+            },
+            axis(0, GamepadAxis::LeftZ, 0.0),
+        ];
+        assert_eq!(coalesce_axes(&mut events), 0);
+        assert_eq!(events.len(), 4);
+    }
+}
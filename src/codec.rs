@@ -0,0 +1,240 @@
+//! Encoding of gamepad events, mappings and settings, available with the `serialize` feature.
+//!
+//! Every event, mapping and settings type of the crate implements [`Serialize`] and [`Deserialize`].
+//! This module provides a compact binary encoding and a human readable JSON form on top of them.
+
+use bevy_input::gamepad::{GamepadAxis, GamepadButton, GamepadInput};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::error::GamepadError;
+
+/// Encode a value in the compact binary form
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, GamepadError> {
+    bincode::serialize(value).map_err(|e| GamepadError::Encode(e.to_string()))
+}
+
+/// Decode a value from the compact binary form
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, GamepadError> {
+    bincode::deserialize(bytes).map_err(|e| GamepadError::Decode(e.to_string()))
+}
+
+/// Encode a value as JSON
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<String, GamepadError> {
+    serde_json::to_string(value).map_err(|e| GamepadError::Encode(e.to_string()))
+}
+
+/// Encode a value as indented JSON, suitable for diffing
+pub fn to_json_pretty<T: Serialize + ?Sized>(value: &T) -> Result<String, GamepadError> {
+    serde_json::to_string_pretty(value).map_err(|e| GamepadError::Encode(e.to_string()))
+}
+
+/// Decode a value from JSON
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, GamepadError> {
+    serde_json::from_str(json).map_err(|e| GamepadError::Decode(e.to_string()))
+}
+
+/// Mirror of [`GamepadInput`], which does not implement serde traits in `bevy_input`
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "GamepadInput")]
+enum GamepadInputDef {
+    Axis(GamepadAxis),
+    Button(GamepadButton),
+}
+
+#[derive(Serialize, Deserialize)]
+struct Input(#[serde(with = "GamepadInputDef")] GamepadInput);
+
+/// Serde adapter for maps keyed by [`GamepadInput`]
+pub(crate) mod input_map {
+    use bevy_utils::HashMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Input;
+    use bevy_input::gamepad::GamepadInput;
+
+    pub fn serialize<V: Serialize + Clone, S: Serializer>(
+        map: &HashMap<GamepadInput, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        map.iter()
+            .map(|(input, value)| (Input(*input), value.clone()))
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, V: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<GamepadInput, V>, D::Error> {
+        Ok(Vec::<(Input, V)>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(Input(input), value)| (input, value))
+            .collect())
+    }
+}
+
+/// Serde adapter for lists of [`GamepadInput`] sets
+pub(crate) mod input_sets {
+    use bevy_utils::HashSet;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Input;
+    use bevy_input::gamepad::GamepadInput;
+
+    pub fn serialize<S: Serializer>(
+        sets: &[HashSet<GamepadInput>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        sets.iter()
+            .map(|set| set.iter().map(|input| Input(*input)).collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<HashSet<GamepadInput>>, D::Error> {
+        Ok(Vec::<Vec<Input>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|set| set.into_iter().map(|Input(input)| input).collect())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::entity::Entity;
+    use bevy_input::gamepad::GamepadConnection;
+
+    use super::*;
+    use crate::{
+        GamepadDeviceInfo, GamepadGroup, GamepadPluginSettings, GamepadSplit, MergePolicy,
+        glyph::{ButtonLabel, ButtonLabels},
+        platform::GamepadPlatformEvent,
+        profile::{ButtonChange, DPadChange, FaceLayout, GamepadChange},
+        timestamp::InputTimestamp,
+    };
+
+    fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> [T; 2] {
+        [
+            from_bytes(&to_bytes(value).unwrap()).unwrap(),
+            from_json(&to_json(value).unwrap()).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn changes() {
+        let changes = vec![
+            GamepadChange::Button(ButtonChange::new(GamepadButton::South, 1.0)),
+            GamepadChange::Buttons(vec![ButtonChange::new(GamepadButton::Other(3), 0.5)]),
+            GamepadChange::DualAxis {
+                x_axis: GamepadAxis::LeftStickX,
+                x_value: -0.25,
+                y_axis: GamepadAxis::LeftStickY,
+                y_value: 0.75,
+            },
+            GamepadChange::SingleAxis {
+                axis: GamepadAxis::Other(1),
+                value: 0.1,
+            },
+            GamepadChange::DPad(DPadChange::new(1.0, 0.0, 0.0, 1.0)),
+        ];
+        for decoded in round_trip(&changes) {
+            assert_eq!(decoded, changes);
+        }
+    }
+
+    #[test]
+    fn events() {
+        let events = vec![
+            GamepadPlatformEvent::Connected {
+                id: 2,
+                connection: GamepadConnection::Connected {
+                    name: String::from("Xbox Wireless Controller"),
+                    vendor_id: Some(0x045e),
+                    product_id: None,
+                },
+                info: GamepadDeviceInfo::new("Xbox", FaceLayout::Standard),
+                labels: ButtonLabels::XBOX,
+            },
+            GamepadPlatformEvent::InputChanged {
+                id: 2,
+                change: GamepadChange::Button(ButtonChange::new(GamepadButton::East, 1.0)),
+                timestamp: InputTimestamp::now(),
+            },
+            GamepadPlatformEvent::Disconnected { id: 2 },
+        ];
+        for decoded in round_trip(&events) {
+            assert_eq!(format!("{decoded:?}"), format!("{events:?}"));
+        }
+    }
+
+    #[test]
+    fn custom_labels() {
+        const CUSTOM: ButtonLabels = ButtonLabels::new(&[
+            (
+                GamepadButton::South,
+                ButtonLabel::new("Punch", "hand.raised")
+                    .with_color(bevy_color::Srgba::rgb(0.9, 0.1, 0.3)),
+            ),
+            (GamepadButton::East, ButtonLabel::new("Kick", "figure.kick")),
+        ]);
+
+        let [bytes, json] = round_trip(&CUSTOM);
+        assert_eq!(bytes, CUSTOM);
+        assert_eq!(json, CUSTOM);
+        assert_eq!(bytes.name(), None);
+
+        // Decoding the same table again reuses the interned labels
+        assert!(std::ptr::eq(
+            bytes.get(GamepadButton::South).unwrap(),
+            json.get(GamepadButton::South).unwrap()
+        ));
+
+        for decoded in round_trip(&ButtonLabels::PLAYSTATION) {
+            assert_eq!(decoded, ButtonLabels::PLAYSTATION);
+        }
+    }
+
+    #[test]
+    fn input_keyed_components() {
+        let group = GamepadGroup::new([Entity::from_raw(1), Entity::from_raw(2)])
+            .with_input_policy(GamepadAxis::LeftStickX, MergePolicy::Sum);
+        for decoded in round_trip(&group) {
+            assert_eq!(decoded.members(), group.members());
+            assert_eq!(decoded.policy(GamepadAxis::LeftStickX), MergePolicy::Sum);
+            assert_eq!(
+                decoded.policy(GamepadButton::South),
+                MergePolicy::MaxMagnitude
+            );
+        }
+
+        let split = GamepadSplit::left_right();
+        for decoded in round_trip(&split) {
+            assert_eq!(decoded.parts(), split.parts());
+        }
+    }
+
+    #[test]
+    fn settings() {
+        let settings = GamepadPluginSettings {
+            coalesce_axes: true,
+            channel_capacity: Some(64),
+            ..Default::default()
+        };
+        for decoded in round_trip(&settings) {
+            assert_eq!(format!("{decoded:?}"), format!("{settings:?}"));
+        }
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(matches!(
+            from_bytes::<GamepadChange>(&[0xff; 3]),
+            Err(GamepadError::Decode(_))
+        ));
+        assert!(matches!(
+            from_json::<GamepadChange>("{"),
+            Err(GamepadError::Decode(_))
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+use bevy_ecs::prelude::*;
+
+use crate::{
+    GamepadGlyphSet, GamepadPluginSettings,
+    profile::{FaceButtonConvention, FaceLayout, JoyConSide, Profile},
+};
+
+/// Information about the physical device and profile backing a gamepad entity
+#[derive(Component, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadDeviceInfo {
+    /// Name of the profile the device was matched to
+    profile: String,
+
+    /// Physical layout of the face buttons
+    face_layout: FaceLayout,
+
+    /// Face button convention currently applied to the device
+    face_buttons: FaceButtonConvention,
+
+    /// Side of a single Joy-Con
+    joy_con: Option<JoyConSide>,
+}
+
+impl GamepadDeviceInfo {
+    pub fn new(profile: impl Into<String>, face_layout: FaceLayout) -> Self {
+        Self {
+            profile: profile.into(),
+            face_layout,
+            face_buttons: FaceButtonConvention::default(),
+            joy_con: None,
+        }
+    }
+
+    #[cfg_attr(not(target_vendor = "apple"), allow(dead_code))]
+    pub(crate) fn from_profile<P: Profile + ?Sized>(profile: &P) -> Self {
+        Self::new(profile.name(), profile.face_layout()).with_joy_con(profile.joy_con())
+    }
+
+    pub(crate) fn with_joy_con(self, joy_con: Option<JoyConSide>) -> Self {
+        Self { joy_con, ..self }
+    }
+
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    pub fn face_layout(&self) -> FaceLayout {
+        self.face_layout
+    }
+
+    pub fn face_buttons(&self) -> FaceButtonConvention {
+        self.face_buttons
+    }
+
+    /// Side of the Joy-Con if the device is a single Joy-Con
+    pub fn joy_con(&self) -> Option<JoyConSide> {
+        self.joy_con
+    }
+}
+
+/// Keep the active [`FaceButtonConvention`] in [`GamepadDeviceInfo`] and [`GamepadGlyphSet`] in sync
+/// with the global setting and any per gamepad override
+pub(crate) fn face_button_system(
+    settings: Res<GamepadPluginSettings>,
+    mut query: Query<(
+        &mut GamepadDeviceInfo,
+        &mut GamepadGlyphSet,
+        Option<&FaceButtonConvention>,
+    )>,
+) {
+    for (mut info, mut glyphs, convention) in query.iter_mut() {
+        let convention = convention.copied().unwrap_or(settings.face_buttons);
+
+        if info.face_buttons != convention {
+            info.face_buttons = convention;
+        }
+
+        if glyphs.face_buttons() != convention {
+            glyphs.set_face_buttons(convention);
+        }
+    }
+}
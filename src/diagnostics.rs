@@ -0,0 +1,119 @@
+use bevy_app::prelude::*;
+use bevy_diagnostic::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore, RegisterDiagnostic,
+};
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time};
+use bevy_utils::{HashMap, Instant};
+
+use crate::{GamepadChannelStats, GamepadPluginSystems};
+
+/// Adds diagnostics of the gamepad input pipeline, to see when the channel from the platform driver
+/// backs up or a profile is missing mappings. Add after [`crate::GamepadPlugin`], and add
+/// `LogDiagnosticsPlugin` to output them to the console.
+///
+/// Each gamepad entity receiving input gets an [`event_rate`](Self::event_rate) diagnostic, which is
+/// disabled once the entity is despawned.
+#[derive(Default)]
+pub struct GamepadDiagnosticsPlugin;
+
+impl Plugin for GamepadDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::EVENTS_RECEIVED))
+            .register_diagnostic(Diagnostic::new(Self::CHANNEL_DEPTH))
+            .register_diagnostic(Diagnostic::new(Self::LATENCY).with_suffix("ms"))
+            .register_diagnostic(
+                Diagnostic::new(Self::UNHANDLED_ELEMENTS).with_smoothing_factor(0.0),
+            )
+            .add_systems(
+                PreUpdate,
+                Self::diagnostic_system.after(GamepadPluginSystems::Platform),
+            );
+    }
+}
+
+impl GamepadDiagnosticsPlugin {
+    /// Events received from the platform driver per frame
+    pub const EVENTS_RECEIVED: DiagnosticPath =
+        DiagnosticPath::const_new("gamepad/events_received");
+
+    /// Events in the channel from the platform driver when it is drained
+    pub const CHANNEL_DEPTH: DiagnosticPath = DiagnosticPath::const_new("gamepad/channel_depth");
+
+    /// Longest time from a driver callback to the platform system of the input received in a frame
+    pub const LATENCY: DiagnosticPath = DiagnosticPath::const_new("gamepad/latency");
+
+    /// Element changes the profiles of the devices have no mapping for, since the plugin was built
+    pub const UNHANDLED_ELEMENTS: DiagnosticPath =
+        DiagnosticPath::const_new("gamepad/unhandled_elements");
+
+    /// Input events per second of a gamepad entity
+    pub fn event_rate(gamepad: Entity) -> DiagnosticPath {
+        DiagnosticPath::new(format!("gamepad/{gamepad}/event_rate"))
+    }
+
+    fn diagnostic_system(
+        mut store: ResMut<DiagnosticsStore>,
+        stats: Res<GamepadChannelStats>,
+        time: Res<Time<Real>>,
+        entities: Query<()>,
+        mut rates: Local<HashMap<Entity, DiagnosticPath>>,
+    ) {
+        measure(
+            &mut store,
+            &Self::EVENTS_RECEIVED,
+            stats.frame_received() as f64,
+        );
+        measure(&mut store, &Self::CHANNEL_DEPTH, stats.depth() as f64);
+        measure(
+            &mut store,
+            &Self::UNHANDLED_ELEMENTS,
+            stats.unhandled() as f64,
+        );
+        if let Some(latency) = stats.latency() {
+            measure(&mut store, &Self::LATENCY, latency.as_secs_f64() * 1000.0);
+        }
+
+        for (gamepad, _) in stats.frame_events() {
+            rates.entry(gamepad).or_insert_with(|| {
+                let path = Self::event_rate(gamepad);
+                store.add(Diagnostic::new(path.clone()));
+                path
+            });
+        }
+
+        // Rates of despawned gamepads would otherwise stay at their last value
+        rates.retain(|gamepad, path| {
+            let exists = entities.contains(*gamepad);
+            if let (false, Some(diagnostic)) = (exists, store.get_mut(path)) {
+                diagnostic.is_enabled = false;
+            }
+            exists
+        });
+
+        let delta_seconds = time.delta_secs_f64();
+        if delta_seconds == 0.0 {
+            return;
+        }
+
+        let events: HashMap<Entity, usize> = stats.frame_events().collect();
+        for (gamepad, path) in rates.iter() {
+            let count = events.get(gamepad).copied().unwrap_or(0);
+            measure(&mut store, path, count as f64 / delta_seconds);
+        }
+    }
+}
+
+/// Add a measurement to an enabled diagnostic. Gamepad rates are registered at runtime, so the store is
+/// written directly instead of through `Diagnostics`.
+fn measure(store: &mut DiagnosticsStore, path: &DiagnosticPath, value: f64) {
+    if let Some(diagnostic) = store
+        .get_mut(path)
+        .filter(|diagnostic| diagnostic.is_enabled)
+    {
+        diagnostic.add_measurement(DiagnosticMeasurement {
+            time: Instant::now(),
+            value,
+        });
+    }
+}
@@ -0,0 +1,270 @@
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::{GamepadAxis, GamepadButton, GamepadInput};
+use bevy_utils::HashSet;
+
+/// Analog input read as a digital button by [`DigitalThresholds`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnalogInput {
+    /// Analog button, such as a trigger
+    Button(GamepadButton),
+
+    /// Positive half of an axis. Right and up for sticks.
+    Positive(GamepadAxis),
+
+    /// Negative half of an axis. Left and down for sticks.
+    Negative(GamepadAxis),
+}
+
+impl AnalogInput {
+    /// Value of the input in `0.0..=1.0` if the changed input is this one
+    fn value(&self, input: GamepadInput, value: f32) -> Option<f32> {
+        match (*self, input) {
+            (Self::Button(button), GamepadInput::Button(changed)) if button == changed => {
+                Some(value)
+            }
+            (Self::Positive(axis), GamepadInput::Axis(changed)) if axis == changed => {
+                Some(value.max(0.0))
+            }
+            (Self::Negative(axis), GamepadInput::Axis(changed)) if axis == changed => {
+                Some((-value).max(0.0))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Press and release thresholds of an analog input read as a digital button. The button is pressed
+/// when the value reaches `press`, and released when it falls to `release`, so a value hovering
+/// around a single threshold does not toggle the button every frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct DigitalThreshold {
+    press: f32,
+    release: f32,
+}
+
+impl DigitalThreshold {
+    /// Thresholds are clamped to `0.0..=1.0`, and the release threshold to at most the press threshold
+    pub fn new(press: f32, release: f32) -> Self {
+        let press = press.clamp(0.0, 1.0);
+        Self {
+            press,
+            release: release.clamp(0.0, press),
+        }
+    }
+
+    /// Value at which the button is pressed
+    pub fn press(&self) -> f32 {
+        self.press
+    }
+
+    /// Value at which the button is released
+    pub fn release(&self) -> f32 {
+        self.release
+    }
+}
+
+impl Default for DigitalThreshold {
+    /// The default thresholds of bevy_input's `ButtonSettings`
+    fn default() -> Self {
+        Self::new(0.75, 0.65)
+    }
+}
+
+/// Read analog buttons and stick directions of a gamepad as digital buttons, with a press and
+/// release threshold pair for each.
+///
+/// Insert this component on a gamepad entity. The digital buttons are sent as
+/// [`GamepadButton::Other`] buttons alongside the analog input, after the profile mapping, so every
+/// platform behaves the same. The default binds the trigger buttons and the four directions of both
+/// sticks. Bind the trigger axes instead if [`crate::TriggerReporting::Axis`] is used.
+///
+/// ```ignore
+/// commands.entity(gamepad).insert(
+///     DigitalThresholds::default()
+///         .with_threshold(AnalogInput::Button(GamepadButton::RightTrigger2), DigitalThreshold::new(0.3, 0.2)),
+/// );
+///
+/// fn fire(gamepads: Query<&Gamepad>) {
+///     for gamepad in gamepads.iter() {
+///         if gamepad.just_pressed(DigitalThresholds::RIGHT_TRIGGER) {
+///             // Fire once per pull of the trigger
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct DigitalThresholds {
+    bindings: Vec<DigitalBinding>,
+
+    /// Digital buttons currently pressed
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pressed: HashSet<GamepadButton>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+struct DigitalBinding {
+    input: AnalogInput,
+    button: GamepadButton,
+    threshold: DigitalThreshold,
+}
+
+impl DigitalThresholds {
+    /// Digital [`GamepadButton::LeftTrigger2`]
+    pub const LEFT_TRIGGER: GamepadButton = GamepadButton::Other(0);
+
+    /// Digital [`GamepadButton::RightTrigger2`]
+    pub const RIGHT_TRIGGER: GamepadButton = GamepadButton::Other(1);
+
+    /// Left stick pushed up
+    pub const LEFT_STICK_UP: GamepadButton = GamepadButton::Other(2);
+
+    /// Left stick pushed down
+    pub const LEFT_STICK_DOWN: GamepadButton = GamepadButton::Other(3);
+
+    /// Left stick pushed left
+    pub const LEFT_STICK_LEFT: GamepadButton = GamepadButton::Other(4);
+
+    /// Left stick pushed right
+    pub const LEFT_STICK_RIGHT: GamepadButton = GamepadButton::Other(5);
+
+    /// Right stick pushed up
+    pub const RIGHT_STICK_UP: GamepadButton = GamepadButton::Other(6);
+
+    /// Right stick pushed down
+    pub const RIGHT_STICK_DOWN: GamepadButton = GamepadButton::Other(7);
+
+    /// Right stick pushed left
+    pub const RIGHT_STICK_LEFT: GamepadButton = GamepadButton::Other(8);
+
+    /// Right stick pushed right
+    pub const RIGHT_STICK_RIGHT: GamepadButton = GamepadButton::Other(9);
+
+    /// No bindings, to bind only the inputs the game reads
+    pub fn empty() -> Self {
+        Self {
+            bindings: Vec::new(),
+            pressed: HashSet::default(),
+        }
+    }
+
+    /// Read an analog input as a digital button, replacing any binding of the input
+    pub fn with(
+        mut self,
+        input: AnalogInput,
+        button: GamepadButton,
+        threshold: DigitalThreshold,
+    ) -> Self {
+        self.bindings.retain(|binding| binding.input != input);
+        self.bindings.push(DigitalBinding {
+            input,
+            button,
+            threshold,
+        });
+        self
+    }
+
+    /// Change the thresholds of a bound analog input
+    pub fn with_threshold(mut self, input: AnalogInput, threshold: DigitalThreshold) -> Self {
+        for binding in self
+            .bindings
+            .iter_mut()
+            .filter(|binding| binding.input == input)
+        {
+            binding.threshold = threshold;
+        }
+        self
+    }
+
+    /// Change the thresholds of every bound analog input
+    pub fn with_all_thresholds(mut self, threshold: DigitalThreshold) -> Self {
+        for binding in self.bindings.iter_mut() {
+            binding.threshold = threshold;
+        }
+        self
+    }
+
+    /// Thresholds of a bound analog input
+    pub fn threshold(&self, input: AnalogInput) -> Option<DigitalThreshold> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.input == input)
+            .map(|binding| binding.threshold)
+    }
+
+    /// Apply an input change, returning the digital buttons which were pressed or released
+    pub(crate) fn update(&mut self, input: GamepadInput, value: f32) -> Vec<(GamepadButton, bool)> {
+        let mut changed = Vec::new();
+        for binding in &self.bindings {
+            let Some(value) = binding.input.value(input, value) else {
+                continue;
+            };
+
+            let pressed = self.pressed.contains(&binding.button);
+            if !pressed && value >= binding.threshold.press {
+                self.pressed.insert(binding.button);
+                changed.push((binding.button, true));
+            } else if pressed && value <= binding.threshold.release {
+                self.pressed.remove(&binding.button);
+                changed.push((binding.button, false));
+            }
+        }
+        changed
+    }
+}
+
+impl Default for DigitalThresholds {
+    /// Both triggers and the four directions of both sticks, with the default thresholds
+    fn default() -> Self {
+        let threshold = DigitalThreshold::default();
+        [
+            (
+                AnalogInput::Button(GamepadButton::LeftTrigger2),
+                Self::LEFT_TRIGGER,
+            ),
+            (
+                AnalogInput::Button(GamepadButton::RightTrigger2),
+                Self::RIGHT_TRIGGER,
+            ),
+            (
+                AnalogInput::Positive(GamepadAxis::LeftStickY),
+                Self::LEFT_STICK_UP,
+            ),
+            (
+                AnalogInput::Negative(GamepadAxis::LeftStickY),
+                Self::LEFT_STICK_DOWN,
+            ),
+            (
+                AnalogInput::Negative(GamepadAxis::LeftStickX),
+                Self::LEFT_STICK_LEFT,
+            ),
+            (
+                AnalogInput::Positive(GamepadAxis::LeftStickX),
+                Self::LEFT_STICK_RIGHT,
+            ),
+            (
+                AnalogInput::Positive(GamepadAxis::RightStickY),
+                Self::RIGHT_STICK_UP,
+            ),
+            (
+                AnalogInput::Negative(GamepadAxis::RightStickY),
+                Self::RIGHT_STICK_DOWN,
+            ),
+            (
+                AnalogInput::Negative(GamepadAxis::RightStickX),
+                Self::RIGHT_STICK_LEFT,
+            ),
+            (
+                AnalogInput::Positive(GamepadAxis::RightStickX),
+                Self::RIGHT_STICK_RIGHT,
+            ),
+        ]
+        .into_iter()
+        .fold(Self::empty(), |thresholds, (input, button)| {
+            thresholds.with(input, button, threshold)
+        })
+    }
+}
@@ -0,0 +1,48 @@
+use bevy_ecs::prelude::*;
+
+use crate::GamepadId;
+
+/// What happens to the entity of a gamepad when its device disconnects
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisconnectPolicy {
+    /// Despawn the entity, in `PostUpdate` once bevy_input has handled the disconnection
+    Despawn,
+
+    /// Keep the entity with a [`Disconnected`] marker. It is reused if a device with the same
+    /// platform id and profile connects again, preserving components added by the game.
+    #[default]
+    KeepMarked,
+
+    /// Keep the entity as it is. A device connecting again gets a new entity.
+    Keep,
+}
+
+/// Marks the entity of a gamepad whose device disconnected, under [`DisconnectPolicy::KeepMarked`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Disconnected {
+    id: GamepadId,
+}
+
+impl Disconnected {
+    pub(crate) fn new(id: GamepadId) -> Self {
+        Self { id }
+    }
+
+    /// Platform id of the device when it disconnected
+    pub fn id(&self) -> GamepadId {
+        self.id
+    }
+}
+
+/// Marks the entity of a gamepad to despawn under [`DisconnectPolicy::Despawn`]. The entity must outlive
+/// the disconnection event, which bevy_input handles later in `PreUpdate`.
+#[derive(Component)]
+pub(crate) struct Despawn;
+
+pub(crate) fn despawn_system(mut commands: Commands, despawned: Query<Entity, With<Despawn>>) {
+    for entity in despawned.iter() {
+        commands.entity(entity).despawn();
+    }
+}
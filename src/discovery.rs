@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time};
+
+use crate::{
+    GamepadErrorEvent,
+    platform::{Platform, PlatformDriver},
+};
+
+/// Control discovery of new wireless controllers, for example from a pairing screen.
+///
+/// ```ignore
+/// fn pairing(mut discovery: EventWriter<GamepadDiscovery>) {
+///     discovery.send(GamepadDiscovery::Start {
+///         timeout: Some(Duration::from_secs(30)),
+///     });
+/// }
+/// ```
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadDiscovery {
+    /// Start looking for new controllers, stopping after the timeout if there is one.
+    /// Restarts the timeout if discovery is already running.
+    Start { timeout: Option<Duration> },
+
+    /// Stop looking for new controllers
+    Stop,
+}
+
+/// Sent when discovery of new wireless controllers starts
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiscoveryStarted {
+    pub timeout: Option<Duration>,
+}
+
+/// Sent when discovery of new wireless controllers finishes
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiscoveryFinished {
+    pub reason: DiscoveryEnd,
+}
+
+/// Why discovery of new wireless controllers finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiscoveryEnd {
+    /// Stopped with [`GamepadDiscovery::Stop`]
+    Stopped,
+
+    /// The timeout of [`GamepadDiscovery::Start`] elapsed
+    TimedOut,
+
+    /// The platform finished discovery by itself
+    Platform,
+}
+
+/// Progress of wireless controller discovery
+#[derive(Resource, Debug, Default, Clone)]
+pub struct GamepadDiscoveryStatus {
+    active: bool,
+    started: Duration,
+    timeout: Option<Duration>,
+    now: Duration,
+}
+
+impl GamepadDiscoveryStatus {
+    /// Whether the platform is looking for new controllers
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Time since discovery started, if it is active
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.active.then(|| self.now.saturating_sub(self.started))
+    }
+
+    /// Time left until discovery times out, if it is active and has a timeout
+    pub fn remaining(&self) -> Option<Duration> {
+        let elapsed = self.elapsed()?;
+        Some(self.timeout?.saturating_sub(elapsed))
+    }
+
+    fn timed_out(&self) -> bool {
+        self.remaining()
+            .is_some_and(|remaining| remaining.is_zero())
+    }
+}
+
+/// Apply discovery commands to the platform driver, and report when discovery starts and finishes
+pub(crate) fn discovery_system<P: Platform>(
+    mut commands: EventReader<GamepadDiscovery>,
+    mut driver: NonSendMut<PlatformDriver<P>>,
+    time: Res<Time<Real>>,
+    mut status: ResMut<GamepadDiscoveryStatus>,
+    mut started_events: EventWriter<DiscoveryStarted>,
+    mut finished_events: EventWriter<DiscoveryFinished>,
+    mut error_events: EventWriter<GamepadErrorEvent>,
+) {
+    let Some(driver) = driver.get_mut() else {
+        return;
+    };
+
+    status.now = time.elapsed();
+
+    // Without commands, discovery can only start on its own, such as at startup
+    let mut start = (!status.active && driver.discovering()).then_some(None);
+    let mut end = None;
+
+    for command in commands.read() {
+        match *command {
+            GamepadDiscovery::Start { timeout } => match driver.start_discovery() {
+                Ok(()) => start = Some(timeout),
+                Err(error) => {
+                    bevy_log::error!("Failed to start gamepad discovery: {error}");
+                    error_events.send(GamepadErrorEvent {
+                        gamepad: None,
+                        error,
+                    });
+                }
+            },
+            GamepadDiscovery::Stop => {
+                driver.stop_discovery();
+                start = None;
+                end = Some(DiscoveryEnd::Stopped);
+            }
+        }
+    }
+
+    if let Some(timeout) = start {
+        if !status.active {
+            started_events.send(DiscoveryStarted { timeout });
+        }
+        status.active = true;
+        status.started = status.now;
+        status.timeout = timeout;
+        end = None;
+    }
+
+    if status.active && end.is_none() {
+        if status.timed_out() {
+            driver.stop_discovery();
+            end = Some(DiscoveryEnd::TimedOut);
+        } else if !driver.discovering() {
+            end = Some(DiscoveryEnd::Platform);
+        }
+    }
+
+    if let Some(reason) = end.filter(|_| status.active) {
+        status.active = false;
+        status.timeout = None;
+        finished_events.send(DiscoveryFinished { reason });
+    }
+}
@@ -0,0 +1,106 @@
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::{GamepadAxis, GamepadInput};
+use bevy_utils::HashMap;
+
+use crate::profile::DPadChange;
+
+/// Report the D-pad of a gamepad as hat style axes in addition to the direction buttons.
+///
+/// Insert this component on a gamepad entity. Right and up are positive, and opposite directions
+/// held together cancel out.
+///
+/// ```ignore
+/// commands.entity(gamepad).insert(DPadAsAxis);
+///
+/// fn walk(gamepads: Query<&Gamepad>) {
+///     for gamepad in gamepads.iter() {
+///         let x = gamepad.get(DPadAsAxis::X).unwrap_or_default();
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct DPadAsAxis;
+
+impl DPadAsAxis {
+    /// Horizontal D-pad axis
+    pub const X: GamepadAxis = GamepadAxis::Other(0);
+
+    /// Vertical D-pad axis
+    pub const Y: GamepadAxis = GamepadAxis::Other(1);
+
+    /// Axis values of a D-pad state
+    pub(crate) fn axes(dpad: &DPadChange) -> [(GamepadInput, f32); 2] {
+        [
+            (Self::X.into(), dpad.right() - dpad.left()),
+            (Self::Y.into(), dpad.up() - dpad.down()),
+        ]
+    }
+}
+
+/// Keep only the inputs whose value changed since the last D-pad change, and record their new values.
+/// D-pad changes report every direction, so unchanged directions would repeat. Inputs start released.
+pub(crate) fn filter_changed(
+    last: &mut HashMap<GamepadInput, f32>,
+    inputs: &mut Vec<(GamepadInput, f32)>,
+) {
+    inputs.retain(|(input, value)| last.insert(*input, *value).unwrap_or(0.0) != *value);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_input::gamepad::GamepadButton;
+
+    use super::*;
+    use crate::profile::GamepadChange;
+
+    /// Inputs of a D-pad change with the hat axes, as emitted for a gamepad with [`DPadAsAxis`]
+    fn inputs(dpad: DPadChange) -> Vec<(GamepadInput, f32)> {
+        let mut inputs = GamepadChange::DPad(dpad).into_inputs();
+        inputs.extend(DPadAsAxis::axes(&dpad));
+        inputs
+    }
+
+    #[test]
+    fn only_changed_directions() {
+        let mut last = HashMap::default();
+
+        let mut up = inputs(DPadChange::new(1.0, 0.0, 0.0, 0.0));
+        filter_changed(&mut last, &mut up);
+        assert_eq!(
+            up,
+            vec![
+                (GamepadButton::DPadUp.into(), 1.0),
+                (DPadAsAxis::Y.into(), 1.0)
+            ]
+        );
+
+        let mut repeated = inputs(DPadChange::new(1.0, 0.0, 0.0, 0.0));
+        filter_changed(&mut last, &mut repeated);
+        assert!(repeated.is_empty());
+
+        let mut up_right = inputs(DPadChange::new(1.0, 0.0, 0.0, 1.0));
+        filter_changed(&mut last, &mut up_right);
+        assert_eq!(
+            up_right,
+            vec![
+                (GamepadButton::DPadRight.into(), 1.0),
+                (DPadAsAxis::X.into(), 1.0)
+            ]
+        );
+
+        let mut released = inputs(DPadChange::new(0.0, 0.0, 0.0, 0.0));
+        filter_changed(&mut last, &mut released);
+        assert_eq!(released.len(), 4);
+        assert!(released.iter().all(|(_, value)| *value == 0.0));
+    }
+
+    #[test]
+    fn opposite_directions_cancel() {
+        let axes = DPadAsAxis::axes(&DPadChange::new(1.0, 1.0, 1.0, 0.0));
+        assert_eq!(
+            axes,
+            [(DPadAsAxis::X.into(), -1.0), (DPadAsAxis::Y.into(), 0.0)]
+        );
+    }
+}
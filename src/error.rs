@@ -1,5 +1,63 @@
-#[derive(thiserror::Error, Debug)]
+use bevy_ecs::prelude::*;
+
+use crate::GamepadId;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum GamepadError {
     #[error("platform: {0}")]
     Platform(String),
+
+    #[error("failed to initialize gamepad {id}: {reason}")]
+    DeviceInit { id: GamepadId, reason: String },
+
+    #[error("unsupported controller: {name}")]
+    UnsupportedController { id: Option<GamepadId>, name: String },
+
+    #[error("gamepad {id} does not support output command {command}")]
+    OutputUnsupported { id: GamepadId, command: String },
+
+    #[error("gamepad event channel closed")]
+    ChannelClosed,
+
+    #[error("mapping parse error: {0}")]
+    MappingParse(String),
+
+    #[error("decoding failed: {0}")]
+    Decode(String),
+
+    #[error("encoding failed: {0}")]
+    Encode(String),
+
+    #[error("io: {0}")]
+    Io(String),
+}
+
+impl GamepadError {
+    /// Platform id of the gamepad the error is about, if any
+    pub fn gamepad_id(&self) -> Option<GamepadId> {
+        match self {
+            GamepadError::DeviceInit { id, .. } | GamepadError::OutputUnsupported { id, .. } => {
+                Some(*id)
+            }
+            GamepadError::UnsupportedController { id, .. } => *id,
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for GamepadError {
+    fn from(error: std::io::Error) -> Self {
+        GamepadError::Io(error.to_string())
+    }
+}
+
+/// Sent for each error reported by the platform driver, so the app can tell the player,
+/// for example that a controller is not supported
+#[derive(Event, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadErrorEvent {
+    /// Entity of the affected gamepad, if the error concerns a connected gamepad
+    pub gamepad: Option<Entity>,
+    pub error: GamepadError,
 }
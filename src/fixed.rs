@@ -0,0 +1,227 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::{GamepadAxis, GamepadButton, GamepadInput};
+use bevy_time::{Fixed, Real, Time, Virtual};
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{history::PRESS_THRESHOLD, timestamp::InputTimestamp};
+
+/// Gamepad state sampled at the fixed timestep, for gameplay running in `FixedUpdate`.
+///
+/// Insert this component on a gamepad entity to enable fixed timestep sampling. Each input received from
+/// the platform is assigned to the fixed tick its capture time falls into, so a press is seen by exactly
+/// one tick no matter how many ticks run in a frame. The state is updated in `FixedPreUpdate`.
+///
+/// ```ignore
+/// commands.entity(gamepad).insert(FixedGamepadInput::default());
+///
+/// fn jump(inputs: Query<&FixedGamepadInput>) {
+///     for input in inputs.iter() {
+///         if input.just_pressed(GamepadButton::South) {
+///             // ...
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Default, Clone)]
+pub struct FixedGamepadInput {
+    /// Inputs received from the platform which have not been reached by a fixed tick yet
+    pending: VecDeque<(InputTimestamp, GamepadInput, f32)>,
+
+    values: HashMap<GamepadInput, f32>,
+    pressed: HashSet<GamepadButton>,
+    just_pressed: HashSet<GamepadButton>,
+    just_released: HashSet<GamepadButton>,
+}
+
+impl FixedGamepadInput {
+    /// The button is held at the end of the current tick
+    pub fn pressed(&self, button: GamepadButton) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    /// The button was pressed during the current tick. It may have been released again in the same tick.
+    pub fn just_pressed(&self, button: GamepadButton) -> bool {
+        self.just_pressed.contains(&button)
+    }
+
+    /// The button was released during the current tick. It may have been pressed again in the same tick.
+    pub fn just_released(&self, button: GamepadButton) -> bool {
+        self.just_released.contains(&button)
+    }
+
+    pub fn get_pressed(&self) -> impl Iterator<Item = &GamepadButton> {
+        self.pressed.iter()
+    }
+
+    pub fn get_just_pressed(&self) -> impl Iterator<Item = &GamepadButton> {
+        self.just_pressed.iter()
+    }
+
+    pub fn get_just_released(&self) -> impl Iterator<Item = &GamepadButton> {
+        self.just_released.iter()
+    }
+
+    /// Value of a button or axis at the end of the current tick
+    pub fn value(&self, input: impl Into<GamepadInput>) -> f32 {
+        self.values.get(&input.into()).copied().unwrap_or(0.0)
+    }
+
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.value(axis)
+    }
+
+    pub(crate) fn push(&mut self, input: GamepadInput, value: f32, timestamp: InputTimestamp) {
+        self.pending.push_back((timestamp, input, value));
+    }
+
+    /// Run a fixed tick ending at `tick_end`, applying the pending inputs captured before its end.
+    /// `time` maps a capture time onto the clock of the ticks.
+    fn tick(&mut self, tick_end: Duration, time: impl Fn(InputTimestamp) -> Duration) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+
+        // Inputs captured before ticks already run this frame are applied in the first tick which sees them
+        while let Some((timestamp, ..)) = self.pending.front() {
+            if time(*timestamp) > tick_end {
+                break;
+            }
+
+            let Some((_, input, value)) = self.pending.pop_front() else {
+                break;
+            };
+            self.apply(input, value);
+        }
+    }
+
+    fn apply(&mut self, input: GamepadInput, value: f32) {
+        self.values.insert(input, value);
+
+        let GamepadInput::Button(button) = input else {
+            return;
+        };
+
+        if value >= PRESS_THRESHOLD {
+            if self.pressed.insert(button) {
+                self.just_pressed.insert(button);
+            }
+        } else if self.pressed.remove(&button) {
+            self.just_released.insert(button);
+        }
+    }
+}
+
+/// Map a capture time onto the virtual clock, relative to the last update of the real clock
+fn virtual_time(
+    timestamp: InputTimestamp,
+    real_time: &Time<Real>,
+    virtual_time: &Time<Virtual>,
+) -> Duration {
+    let Some(last_update) = real_time.last_update() else {
+        return Duration::ZERO;
+    };
+
+    let age = last_update.saturating_duration_since(timestamp.instant());
+    virtual_time
+        .elapsed()
+        .saturating_sub(age.mul_f64(virtual_time.effective_speed_f64()))
+}
+
+/// Apply the pending inputs captured before the end of the current fixed tick
+pub(crate) fn fixed_input_system(
+    fixed_time: Res<Time<Fixed>>,
+    real_time: Res<Time<Real>>,
+    virtual_clock: Res<Time<Virtual>>,
+    mut inputs: Query<&mut FixedGamepadInput>,
+) {
+    let tick_end = fixed_time.elapsed();
+
+    for mut input in inputs.iter_mut() {
+        input.tick(tick_end, |timestamp| {
+            virtual_time(timestamp, &real_time, &virtual_clock)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOUTH: GamepadButton = GamepadButton::South;
+
+    /// Input pushed at a capture time in milliseconds
+    fn push(input: &mut FixedGamepadInput, millis: u64, value: f32) {
+        let timestamp = InputTimestamp::from_captured(Duration::from_millis(millis));
+        input.push(SOUTH.into(), value, timestamp);
+    }
+
+    /// Run the tick ending at a time in milliseconds, with capture times on the clock of the ticks
+    fn tick(input: &mut FixedGamepadInput, millis: u64) {
+        input.tick(Duration::from_millis(millis), |timestamp| {
+            timestamp.captured()
+        });
+    }
+
+    fn state(input: &FixedGamepadInput) -> [bool; 3] {
+        [
+            input.just_pressed(SOUTH),
+            input.pressed(SOUTH),
+            input.just_released(SOUTH),
+        ]
+    }
+
+    #[test]
+    fn press_and_release_in_one_tick() {
+        let mut input = FixedGamepadInput::default();
+        push(&mut input, 5, 1.0);
+        push(&mut input, 10, 0.0);
+
+        tick(&mut input, 16);
+        assert_eq!(state(&input), [true, false, true]);
+        assert_eq!(input.value(SOUTH), 0.0);
+
+        tick(&mut input, 32);
+        assert_eq!(state(&input), [false, false, false]);
+    }
+
+    #[test]
+    fn press_spanning_two_ticks() {
+        let mut input = FixedGamepadInput::default();
+        push(&mut input, 10, 1.0);
+        push(&mut input, 20, 0.0);
+
+        tick(&mut input, 16);
+        assert_eq!(state(&input), [true, true, false]);
+
+        tick(&mut input, 32);
+        assert_eq!(state(&input), [false, false, true]);
+    }
+
+    #[test]
+    fn several_ticks_in_one_frame() {
+        let mut input = FixedGamepadInput::default();
+        push(&mut input, 5, 1.0);
+        push(&mut input, 40, 0.0);
+
+        // The press is seen by one tick only, and the release waits for the tick it was captured in
+        tick(&mut input, 16);
+        assert_eq!(state(&input), [true, true, false]);
+        tick(&mut input, 32);
+        assert_eq!(state(&input), [false, true, false]);
+        tick(&mut input, 48);
+        assert_eq!(state(&input), [false, false, true]);
+    }
+
+    #[test]
+    fn late_input_applied_in_next_tick() {
+        let mut input = FixedGamepadInput::default();
+        tick(&mut input, 16);
+
+        // Captured during a tick which has already run
+        push(&mut input, 10, 1.0);
+        tick(&mut input, 32);
+        assert_eq!(state(&input), [true, true, false]);
+        assert!(input.pending.is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::GamepadInput;
+use bevy_utils::HashMap;
+use bevy_window::Window;
+
+use crate::{
+    GamepadPlatformHandler, GamepadPluginSettings, GamepadResync, InputSinks,
+    timestamp::InputTimestamp,
+};
+
+/// What happens to gamepad input while no window of the app is focused.
+///
+/// The platform keeps reporting input in the background, so an unfocused game would otherwise
+/// react to a controller used for another app. Unless input is delivered in the background, every
+/// input is updated to the actual state of the devices when focus is regained.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum FocusPolicy {
+    /// Keep delivering input in the background
+    #[default]
+    Background,
+
+    /// Ignore input while unfocused. Inputs held when focus was lost stay held until it is regained.
+    Ignore,
+
+    /// Release all held buttons and centre all axes when focus is lost, and ignore input until it is
+    /// regained
+    Neutralize,
+}
+
+/// Values of every input sent to the game, and the actual values reported by the devices
+#[derive(Default)]
+pub(crate) struct FocusState {
+    /// Input values are recorded, because the focus policy withholds input while unfocused
+    tracking: bool,
+
+    /// Input is withheld from the game because the app is unfocused
+    suppressed: bool,
+    sent: HashMap<(Entity, GamepadInput), f32>,
+    actual: HashMap<(Entity, GamepadInput), f32>,
+}
+
+impl FocusState {
+    /// Record input values only while the policy needs them to update the game on refocus
+    fn track(&mut self, tracking: bool) {
+        if !tracking {
+            self.sent.clear();
+            self.actual.clear();
+        }
+        self.tracking = tracking;
+    }
+
+    /// Record an input value reported by a device, returning whether it should be sent to the game
+    pub(crate) fn update(&mut self, gamepad: Entity, input: GamepadInput, value: f32) -> bool {
+        if !self.tracking {
+            return true;
+        }
+
+        self.actual.insert((gamepad, input), value);
+        if !self.suppressed {
+            self.sent.insert((gamepad, input), value);
+        }
+        !self.suppressed
+    }
+
+    /// Forget the values of a gamepad which no longer receives input
+    pub(crate) fn forget(&mut self, gamepad: Entity) {
+        self.sent.retain(|(entity, _), _| *entity != gamepad);
+        self.actual.retain(|(entity, _), _| *entity != gamepad);
+    }
+
+    /// Release every input sent to the game, returning the inputs which changed
+    fn neutralize(&mut self) -> Vec<(Entity, GamepadInput, f32)> {
+        self.sent
+            .iter_mut()
+            .filter(|(_, value)| **value != 0.0)
+            .map(|(&(gamepad, input), value)| {
+                *value = 0.0;
+                (gamepad, input, 0.0)
+            })
+            .collect()
+    }
+
+    /// Update every input sent to the game to its actual value, returning the inputs which changed
+    fn resync(&mut self) -> Vec<(Entity, GamepadInput, f32)> {
+        let mut changed = Vec::new();
+        for (&(gamepad, input), &value) in self.actual.iter() {
+            if self.sent.insert((gamepad, input), value) != Some(value) {
+                changed.push((gamepad, input, value));
+            }
+        }
+        changed
+    }
+}
+
+/// Apply the [`FocusPolicy`] when the app loses or regains focus. Apps without windows are always
+/// focused.
+pub(crate) fn focus_system(
+    mut handler: ResMut<GamepadPlatformHandler>,
+    settings: Res<GamepadPluginSettings>,
+    windows: Query<&Window>,
+    mut sinks: InputSinks,
+    mut resync: EventWriter<GamepadResync>,
+) {
+    let tracking = settings.focus_policy != FocusPolicy::Background;
+    if tracking != handler.focus.tracking {
+        handler.focus.track(tracking);
+    }
+
+    let focused = windows.is_empty() || windows.iter().any(|window| window.focused);
+    let suppress = !focused && settings.focus_policy != FocusPolicy::Background;
+    if suppress == handler.focus.suppressed {
+        return;
+    }
+    handler.focus.suppressed = suppress;
+
+    let changed = match (suppress, settings.focus_policy) {
+        (true, FocusPolicy::Neutralize) => handler.focus.neutralize(),
+        (true, _) => Vec::new(),
+        (false, _) => handler.focus.resync(),
+    };
+
+    bevy_log::debug!(
+        focused,
+        changed = changed.len(),
+        "Gamepad input focus changed"
+    );
+
+    let timestamp = InputTimestamp::now();
+    for (gamepad, input, value) in changed {
+        sinks.emit(gamepad, input, value, timestamp);
+    }
+
+    // Read the devices too, in case the platform stopped reporting input in the background
+    if !suppress {
+        resync.send(GamepadResync::all());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_input::gamepad::GamepadButton;
+
+    use super::*;
+
+    const SOUTH: GamepadInput = GamepadInput::Button(GamepadButton::South);
+
+    #[test]
+    fn background_records_nothing() {
+        let mut focus = FocusState::default();
+        assert!(focus.update(Entity::PLACEHOLDER, SOUTH, 1.0));
+        assert!(focus.sent.is_empty());
+        assert!(focus.actual.is_empty());
+    }
+
+    #[test]
+    fn resync_after_suppressed_input() {
+        let mut focus = FocusState::default();
+        focus.track(true);
+        assert!(focus.update(Entity::PLACEHOLDER, SOUTH, 1.0));
+
+        focus.suppressed = true;
+        assert!(!focus.update(Entity::PLACEHOLDER, SOUTH, 0.0));
+        assert_eq!(focus.resync(), vec![(Entity::PLACEHOLDER, SOUTH, 0.0)]);
+
+        focus.track(false);
+        assert!(focus.sent.is_empty());
+        assert!(focus.actual.is_empty());
+    }
+}
@@ -0,0 +1,655 @@
+use std::{
+    collections::BTreeSet,
+    sync::{Mutex, PoisonError},
+};
+
+use bevy_color::Srgba;
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::GamepadButton;
+
+use crate::profile::{FaceButtonConvention, FaceLayout};
+
+/// Display metadata for a single [`GamepadButton`] on a specific controller family
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct ButtonLabel {
+    /// Name printed on the button, or the name the vendor uses for it
+    pub name: &'static str,
+
+    /// SF Symbol style glyph identifier which can be used to look up an icon
+    pub glyph: &'static str,
+
+    /// Colour of the face of the button, if the vendor colours it
+    pub color: Option<Srgba>,
+}
+
+impl ButtonLabel {
+    pub const fn new(name: &'static str, glyph: &'static str) -> Self {
+        Self {
+            name,
+            glyph,
+            color: None,
+        }
+    }
+
+    pub const fn with_color(self, color: Srgba) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+}
+
+/// Table of [`ButtonLabel`] for each [`GamepadButton`] provided by a controller profile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ButtonLabels(&'static [(GamepadButton, ButtonLabel)]);
+
+impl ButtonLabels {
+    /// Custom table, for controllers without a built in one
+    pub const fn new(labels: &'static [(GamepadButton, ButtonLabel)]) -> Self {
+        Self(labels)
+    }
+
+    /// Xbox Wireless Controller and compatibles
+    pub const XBOX: Self = Self(&[
+        (
+            GamepadButton::South,
+            ButtonLabel::new("A", "a.circle").with_color(Srgba::rgb(0.38, 0.72, 0.27)),
+        ),
+        (
+            GamepadButton::East,
+            ButtonLabel::new("B", "b.circle").with_color(Srgba::rgb(0.89, 0.20, 0.15)),
+        ),
+        (
+            GamepadButton::West,
+            ButtonLabel::new("X", "x.circle").with_color(Srgba::rgb(0.00, 0.47, 0.84)),
+        ),
+        (
+            GamepadButton::North,
+            ButtonLabel::new("Y", "y.circle").with_color(Srgba::rgb(0.99, 0.73, 0.05)),
+        ),
+        (
+            GamepadButton::LeftTrigger,
+            ButtonLabel::new("LB", "lb.rectangle.roundedbottom"),
+        ),
+        (
+            GamepadButton::RightTrigger,
+            ButtonLabel::new("RB", "rb.rectangle.roundedbottom"),
+        ),
+        (
+            GamepadButton::LeftTrigger2,
+            ButtonLabel::new("LT", "lt.rectangle.roundedtop"),
+        ),
+        (
+            GamepadButton::RightTrigger2,
+            ButtonLabel::new("RT", "rt.rectangle.roundedtop"),
+        ),
+        (
+            GamepadButton::Select,
+            ButtonLabel::new("View", "rectangle.on.rectangle.circle"),
+        ),
+        (
+            GamepadButton::Start,
+            ButtonLabel::new("Menu", "line.3.horizontal.circle"),
+        ),
+        (GamepadButton::Mode, ButtonLabel::new("Xbox", "xbox.logo")),
+        (
+            GamepadButton::C,
+            ButtonLabel::new("Share", "square.and.arrow.up.circle"),
+        ),
+        (
+            GamepadButton::LeftThumb,
+            ButtonLabel::new("LS", "l.joystick.press.down"),
+        ),
+        (
+            GamepadButton::RightThumb,
+            ButtonLabel::new("RS", "r.joystick.press.down"),
+        ),
+        (
+            GamepadButton::DPadUp,
+            ButtonLabel::new("Up", "dpad.up.filled"),
+        ),
+        (
+            GamepadButton::DPadDown,
+            ButtonLabel::new("Down", "dpad.down.filled"),
+        ),
+        (
+            GamepadButton::DPadLeft,
+            ButtonLabel::new("Left", "dpad.left.filled"),
+        ),
+        (
+            GamepadButton::DPadRight,
+            ButtonLabel::new("Right", "dpad.right.filled"),
+        ),
+    ]);
+
+    /// DualShock 4 and DualSense
+    pub const PLAYSTATION: Self = Self(&[
+        (
+            GamepadButton::South,
+            ButtonLabel::new("Cross", "xmark.circle").with_color(Srgba::rgb(0.49, 0.70, 0.91)),
+        ),
+        (
+            GamepadButton::East,
+            ButtonLabel::new("Circle", "circle.circle").with_color(Srgba::rgb(1.00, 0.40, 0.40)),
+        ),
+        (
+            GamepadButton::West,
+            ButtonLabel::new("Square", "square.circle").with_color(Srgba::rgb(1.00, 0.41, 0.97)),
+        ),
+        (
+            GamepadButton::North,
+            ButtonLabel::new("Triangle", "triangle.circle")
+                .with_color(Srgba::rgb(0.25, 0.89, 0.63)),
+        ),
+        (
+            GamepadButton::LeftTrigger,
+            ButtonLabel::new("L1", "l1.rectangle.roundedbottom"),
+        ),
+        (
+            GamepadButton::RightTrigger,
+            ButtonLabel::new("R1", "r1.rectangle.roundedbottom"),
+        ),
+        (
+            GamepadButton::LeftTrigger2,
+            ButtonLabel::new("L2", "l2.rectangle.roundedtop"),
+        ),
+        (
+            GamepadButton::RightTrigger2,
+            ButtonLabel::new("R2", "r2.rectangle.roundedtop"),
+        ),
+        (
+            GamepadButton::Select,
+            ButtonLabel::new("Share", "square.and.arrow.up"),
+        ),
+        (
+            GamepadButton::Start,
+            ButtonLabel::new("Options", "line.3.horizontal"),
+        ),
+        (
+            GamepadButton::Mode,
+            ButtonLabel::new("PS", "playstation.logo"),
+        ),
+        (GamepadButton::C, ButtonLabel::new("Touchpad", "hand.tap")),
+        (
+            GamepadButton::LeftThumb,
+            ButtonLabel::new("L3", "l.joystick.press.down"),
+        ),
+        (
+            GamepadButton::RightThumb,
+            ButtonLabel::new("R3", "r.joystick.press.down"),
+        ),
+        (
+            GamepadButton::DPadUp,
+            ButtonLabel::new("Up", "dpad.up.filled"),
+        ),
+        (
+            GamepadButton::DPadDown,
+            ButtonLabel::new("Down", "dpad.down.filled"),
+        ),
+        (
+            GamepadButton::DPadLeft,
+            ButtonLabel::new("Left", "dpad.left.filled"),
+        ),
+        (
+            GamepadButton::DPadRight,
+            ButtonLabel::new("Right", "dpad.right.filled"),
+        ),
+    ]);
+
+    /// Nintendo Switch Pro Controller, Joy-Con and compatibles, mapped with [`FaceButtonConvention::Positional`]
+    pub const NINTENDO: Self = Self(&[
+        (GamepadButton::South, ButtonLabel::new("B", "b.circle")),
+        (GamepadButton::East, ButtonLabel::new("A", "a.circle")),
+        (GamepadButton::West, ButtonLabel::new("Y", "y.circle")),
+        (GamepadButton::North, ButtonLabel::new("X", "x.circle")),
+        (
+            GamepadButton::LeftTrigger,
+            ButtonLabel::new("L", "l.rectangle.roundedbottom"),
+        ),
+        (
+            GamepadButton::RightTrigger,
+            ButtonLabel::new("R", "r.rectangle.roundedbottom"),
+        ),
+        (
+            GamepadButton::LeftTrigger2,
+            ButtonLabel::new("ZL", "zl.rectangle.roundedtop"),
+        ),
+        (
+            GamepadButton::RightTrigger2,
+            ButtonLabel::new("ZR", "zr.rectangle.roundedtop"),
+        ),
+        (GamepadButton::Select, ButtonLabel::new("-", "minus.circle")),
+        (GamepadButton::Start, ButtonLabel::new("+", "plus.circle")),
+        (
+            GamepadButton::Mode,
+            ButtonLabel::new("Home", "house.circle"),
+        ),
+        (
+            GamepadButton::C,
+            ButtonLabel::new("Capture", "circle.square"),
+        ),
+        (
+            GamepadButton::LeftThumb,
+            ButtonLabel::new("LS", "l.joystick.press.down"),
+        ),
+        (
+            GamepadButton::RightThumb,
+            ButtonLabel::new("RS", "r.joystick.press.down"),
+        ),
+        (
+            GamepadButton::DPadUp,
+            ButtonLabel::new("Up", "dpad.up.filled"),
+        ),
+        (
+            GamepadButton::DPadDown,
+            ButtonLabel::new("Down", "dpad.down.filled"),
+        ),
+        (
+            GamepadButton::DPadLeft,
+            ButtonLabel::new("Left", "dpad.left.filled"),
+        ),
+        (
+            GamepadButton::DPadRight,
+            ButtonLabel::new("Right", "dpad.right.filled"),
+        ),
+    ]);
+
+    /// Single left Joy-Con held sideways
+    pub const JOY_CON_LEFT_SIDEWAYS: Self = Self(&[
+        (
+            GamepadButton::South,
+            ButtonLabel::new("Left", "arrowtriangle.left.circle"),
+        ),
+        (
+            GamepadButton::East,
+            ButtonLabel::new("Down", "arrowtriangle.down.circle"),
+        ),
+        (
+            GamepadButton::West,
+            ButtonLabel::new("Up", "arrowtriangle.up.circle"),
+        ),
+        (
+            GamepadButton::North,
+            ButtonLabel::new("Right", "arrowtriangle.right.circle"),
+        ),
+        (
+            GamepadButton::LeftTrigger,
+            ButtonLabel::new("L", "l.rectangle.roundedbottom"),
+        ),
+        (
+            GamepadButton::LeftTrigger2,
+            ButtonLabel::new("ZL", "zl.rectangle.roundedtop"),
+        ),
+        (GamepadButton::Start, ButtonLabel::new("-", "minus.circle")),
+        (
+            GamepadButton::C,
+            ButtonLabel::new("Capture", "circle.square"),
+        ),
+        (
+            GamepadButton::LeftThumb,
+            ButtonLabel::new("Stick", "l.joystick.press.down"),
+        ),
+    ]);
+
+    /// Single right Joy-Con held sideways
+    pub const JOY_CON_RIGHT_SIDEWAYS: Self = Self(&[
+        (GamepadButton::South, ButtonLabel::new("A", "a.circle")),
+        (GamepadButton::East, ButtonLabel::new("X", "x.circle")),
+        (GamepadButton::West, ButtonLabel::new("B", "b.circle")),
+        (GamepadButton::North, ButtonLabel::new("Y", "y.circle")),
+        (
+            GamepadButton::RightTrigger,
+            ButtonLabel::new("R", "r.rectangle.roundedbottom"),
+        ),
+        (
+            GamepadButton::RightTrigger2,
+            ButtonLabel::new("ZR", "zr.rectangle.roundedtop"),
+        ),
+        (GamepadButton::Start, ButtonLabel::new("+", "plus.circle")),
+        (
+            GamepadButton::Mode,
+            ButtonLabel::new("Home", "house.circle"),
+        ),
+        (
+            GamepadButton::LeftThumb,
+            ButtonLabel::new("Stick", "r.joystick.press.down"),
+        ),
+    ]);
+
+    /// Fallback for controllers without a known vendor layout. Uses the Apple extended gamepad names.
+    pub const GENERIC: Self = Self(&[
+        (GamepadButton::South, ButtonLabel::new("A", "a.circle")),
+        (GamepadButton::East, ButtonLabel::new("B", "b.circle")),
+        (GamepadButton::West, ButtonLabel::new("X", "x.circle")),
+        (GamepadButton::North, ButtonLabel::new("Y", "y.circle")),
+        (
+            GamepadButton::LeftTrigger,
+            ButtonLabel::new("L1", "l1.rectangle.roundedbottom"),
+        ),
+        (
+            GamepadButton::RightTrigger,
+            ButtonLabel::new("R1", "r1.rectangle.roundedbottom"),
+        ),
+        (
+            GamepadButton::LeftTrigger2,
+            ButtonLabel::new("L2", "l2.rectangle.roundedtop"),
+        ),
+        (
+            GamepadButton::RightTrigger2,
+            ButtonLabel::new("R2", "r2.rectangle.roundedtop"),
+        ),
+        (
+            GamepadButton::Select,
+            ButtonLabel::new("Options", "ellipsis.circle"),
+        ),
+        (
+            GamepadButton::Start,
+            ButtonLabel::new("Menu", "line.3.horizontal.circle"),
+        ),
+        (
+            GamepadButton::LeftThumb,
+            ButtonLabel::new("L3", "l.joystick.press.down"),
+        ),
+        (
+            GamepadButton::RightThumb,
+            ButtonLabel::new("R3", "r.joystick.press.down"),
+        ),
+        (
+            GamepadButton::DPadUp,
+            ButtonLabel::new("Up", "dpad.up.filled"),
+        ),
+        (
+            GamepadButton::DPadDown,
+            ButtonLabel::new("Down", "dpad.down.filled"),
+        ),
+        (
+            GamepadButton::DPadLeft,
+            ButtonLabel::new("Left", "dpad.left.filled"),
+        ),
+        (
+            GamepadButton::DPadRight,
+            ButtonLabel::new("Right", "dpad.right.filled"),
+        ),
+    ]);
+
+    /// All built in label tables, with a stable name used when recording
+    const NAMED: [(&'static str, Self); 6] = [
+        ("xbox", Self::XBOX),
+        ("playstation", Self::PLAYSTATION),
+        ("nintendo", Self::NINTENDO),
+        ("joy_con_left_sideways", Self::JOY_CON_LEFT_SIDEWAYS),
+        ("joy_con_right_sideways", Self::JOY_CON_RIGHT_SIDEWAYS),
+        ("generic", Self::GENERIC),
+    ];
+
+    /// Stable name of a built in label table
+    pub fn name(&self) -> Option<&'static str> {
+        Self::NAMED
+            .iter()
+            .find(|(_, labels)| labels == self)
+            .map(|(name, _)| *name)
+    }
+
+    /// Look up a built in label table by the name returned from [`ButtonLabels::name`]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::NAMED
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, labels)| *labels)
+    }
+
+    /// Label table read at runtime, such as from a recording. Label tables are `'static`, so the table
+    /// is kept for the rest of the program, but only once: an equal table made before is returned instead.
+    pub(crate) fn intern(labels: Vec<(GamepadButton, ButtonLabel)>) -> Self {
+        static TABLES: Mutex<Vec<ButtonLabels>> = Mutex::new(Vec::new());
+
+        if let Some((_, named)) = Self::NAMED.iter().find(|(_, named)| named.0 == labels) {
+            return *named;
+        }
+
+        let mut tables = TABLES.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(table) = tables.iter().find(|table| table.0 == labels) {
+            return *table;
+        }
+
+        let table = Self(Box::leak(labels.into_boxed_slice()));
+        tables.push(table);
+        table
+    }
+
+    /// Get the label for a button, if the profile has one
+    pub fn get(&self, button: GamepadButton) -> Option<&'static ButtonLabel> {
+        self.0
+            .iter()
+            .find(|(b, _)| *b == button)
+            .map(|(_, label)| label)
+    }
+
+    /// Iterate over all labelled buttons in the table
+    pub fn iter(&self) -> impl Iterator<Item = (GamepadButton, &'static ButtonLabel)> {
+        self.0.iter().map(|(button, label)| (*button, label))
+    }
+}
+
+/// Text of a label read at runtime, kept once for the rest of the program like the tables in
+/// [`ButtonLabels::intern`]
+pub(crate) fn intern_str(text: String) -> &'static str {
+    static STRINGS: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+    let mut strings = STRINGS.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(interned) = strings.get(text.as_str()) {
+        return interned;
+    }
+
+    let interned = Box::leak(text.into_boxed_str());
+    strings.insert(interned);
+    interned
+}
+
+/// Serialized form of [`ButtonLabels`]. Built in tables are written by their [`ButtonLabels::name`],
+/// other tables with all their labels.
+#[cfg(feature = "serialize")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum LabelsData {
+    Named(String),
+    Custom(Vec<(GamepadButton, LabelData)>),
+}
+
+#[cfg(feature = "serialize")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LabelData {
+    name: String,
+    glyph: String,
+    color: Option<Srgba>,
+}
+
+#[cfg(feature = "serialize")]
+impl serde::Serialize for ButtonLabels {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let data = match self.name() {
+            Some(name) => LabelsData::Named(String::from(name)),
+            None => LabelsData::Custom(
+                self.iter()
+                    .map(|(button, label)| {
+                        let label = LabelData {
+                            name: String::from(label.name),
+                            glyph: String::from(label.glyph),
+                            color: label.color,
+                        };
+                        (button, label)
+                    })
+                    .collect(),
+            ),
+        };
+        data.serialize(serializer)
+    }
+}
+
+/// Custom tables are interned with [`ButtonLabels::intern`]
+#[cfg(feature = "serialize")]
+impl<'de> serde::Deserialize<'de> for ButtonLabels {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match LabelsData::deserialize(deserializer)? {
+            LabelsData::Named(name) => Self::from_name(&name)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown button labels {name}"))),
+            LabelsData::Custom(labels) => Ok(Self::intern(
+                labels
+                    .into_iter()
+                    .map(|(button, label)| {
+                        let name = intern_str(label.name);
+                        let glyph = intern_str(label.glyph);
+                        let label = match label.color {
+                            Some(color) => ButtonLabel::new(name, glyph).with_color(color),
+                            None => ButtonLabel::new(name, glyph),
+                        };
+                        (button, label)
+                    })
+                    .collect(),
+            )),
+        }
+    }
+}
+
+/// Button prompt metadata for a connected gamepad, inserted on the gamepad entity at connection.
+///
+/// Use this to render the correct name, glyph and colour for a [`GamepadButton`], for example
+/// "Cross" on a DualSense, "A" on an Xbox controller and "B" on a Switch Pro Controller for [`GamepadButton::South`].
+///
+/// Labels follow the [`FaceButtonConvention`] active on the gamepad.
+#[derive(Component, Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadGlyphSet {
+    labels: ButtonLabels,
+    face_layout: FaceLayout,
+    face_buttons: FaceButtonConvention,
+}
+
+impl GamepadGlyphSet {
+    pub fn new(labels: ButtonLabels, face_layout: FaceLayout) -> Self {
+        Self {
+            labels,
+            face_layout,
+            face_buttons: FaceButtonConvention::default(),
+        }
+    }
+
+    /// The label table of the profile this gamepad was matched to
+    pub fn labels(&self) -> ButtonLabels {
+        self.labels
+    }
+
+    /// The face button convention the labels are resolved with
+    pub fn face_buttons(&self) -> FaceButtonConvention {
+        self.face_buttons
+    }
+
+    pub(crate) fn set_face_buttons(&mut self, face_buttons: FaceButtonConvention) {
+        self.face_buttons = face_buttons;
+    }
+
+    pub fn label(&self, button: GamepadButton) -> Option<&'static ButtonLabel> {
+        // The label tables are positional, and the convention remap is its own inverse
+        self.labels
+            .get(self.face_buttons.apply(self.face_layout, button))
+    }
+
+    pub fn name(&self, button: GamepadButton) -> Option<&'static str> {
+        self.label(button).map(|label| label.name)
+    }
+
+    pub fn glyph(&self, button: GamepadButton) -> Option<&'static str> {
+        self.label(button).map(|label| label.glyph)
+    }
+
+    pub fn color(&self, button: GamepadButton) -> Option<Srgba> {
+        self.label(button).and_then(|label| label.color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLES: [ButtonLabels; 6] = [
+        ButtonLabels::XBOX,
+        ButtonLabels::PLAYSTATION,
+        ButtonLabels::NINTENDO,
+        ButtonLabels::JOY_CON_LEFT_SIDEWAYS,
+        ButtonLabels::JOY_CON_RIGHT_SIDEWAYS,
+        ButtonLabels::GENERIC,
+    ];
+
+    #[test]
+    fn south_label_of_each_vendor() {
+        let south = |labels: ButtonLabels| labels.get(GamepadButton::South).unwrap().name;
+        assert_eq!(south(ButtonLabels::PLAYSTATION), "Cross");
+        assert_eq!(south(ButtonLabels::XBOX), "A");
+        assert_eq!(south(ButtonLabels::NINTENDO), "B");
+        assert_eq!(south(ButtonLabels::GENERIC), "A");
+    }
+
+    #[test]
+    fn each_button_labelled_once() {
+        for labels in TABLES {
+            let mut buttons: Vec<_> = labels.iter().map(|(button, _)| button).collect();
+            let count = buttons.len();
+            buttons.sort_by_key(|button| format!("{button:?}"));
+            buttons.dedup();
+            assert_eq!(buttons.len(), count, "{:?}", labels.name());
+
+            for (button, label) in labels.iter() {
+                assert!(!label.name.is_empty() && !label.glyph.is_empty());
+                assert_eq!(labels.get(button), Some(label));
+            }
+        }
+    }
+
+    #[test]
+    fn built_in_tables_named() {
+        for labels in TABLES {
+            let name = labels.name().unwrap();
+            assert_eq!(ButtonLabels::from_name(name), Some(labels));
+        }
+        assert_eq!(ButtonLabels::from_name("custom"), None);
+
+        const CUSTOM: ButtonLabels =
+            ButtonLabels::new(&[(GamepadButton::South, ButtonLabel::new("Jump", "j.circle"))]);
+        assert_eq!(CUSTOM.name(), None);
+    }
+
+    #[test]
+    fn glyph_set_resolves_labels() {
+        let glyphs = GamepadGlyphSet::new(ButtonLabels::PLAYSTATION, FaceLayout::Standard);
+        assert_eq!(glyphs.name(GamepadButton::North), Some("Triangle"));
+        assert_eq!(glyphs.glyph(GamepadButton::North), Some("triangle.circle"));
+        assert_eq!(
+            glyphs.color(GamepadButton::East),
+            Some(Srgba::rgb(1.00, 0.40, 0.40))
+        );
+        assert_eq!(glyphs.color(GamepadButton::Start), None);
+        assert_eq!(glyphs.label(GamepadButton::Z), None);
+    }
+
+    #[test]
+    fn glyph_set_follows_convention() {
+        let mut glyphs = GamepadGlyphSet::new(ButtonLabels::NINTENDO, FaceLayout::Nintendo);
+        assert_eq!(glyphs.face_buttons(), FaceButtonConvention::Positional);
+        assert_eq!(glyphs.name(GamepadButton::South), Some("B"));
+        assert_eq!(glyphs.name(GamepadButton::North), Some("X"));
+
+        // The button labelled A is South when mapped by label
+        glyphs.set_face_buttons(FaceButtonConvention::Labelled);
+        assert_eq!(glyphs.name(GamepadButton::South), Some("A"));
+        assert_eq!(glyphs.name(GamepadButton::East), Some("B"));
+        assert_eq!(glyphs.name(GamepadButton::West), Some("X"));
+        assert_eq!(glyphs.name(GamepadButton::North), Some("Y"));
+        assert_eq!(glyphs.name(GamepadButton::Start), Some("+"));
+
+        let mut glyphs = GamepadGlyphSet::new(ButtonLabels::XBOX, FaceLayout::Standard);
+        glyphs.set_face_buttons(FaceButtonConvention::Labelled);
+        assert_eq!(glyphs.name(GamepadButton::South), Some("A"));
+        assert_eq!(glyphs.name(GamepadButton::East), Some("B"));
+    }
+}
@@ -0,0 +1,304 @@
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadInput};
+use bevy_utils::HashMap;
+
+use crate::{GamepadId, GamepadPlatformHandler};
+
+/// How the values of one element from several physical devices are merged in a [`GamepadGroup`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum MergePolicy {
+    /// Use the value with the largest magnitude
+    #[default]
+    MaxMagnitude,
+
+    /// Add the values together, clamped to the range of the element
+    Sum,
+
+    /// Use the value of the first member, in group order, which is not at rest
+    FirstActive,
+}
+
+impl MergePolicy {
+    /// Merge the values of an element from each member of a group, in group order
+    pub fn merge(self, input: GamepadInput, values: impl IntoIterator<Item = f32>) -> f32 {
+        let mut values = values.into_iter();
+        match self {
+            MergePolicy::MaxMagnitude => values.fold(0.0, |merged: f32, value| {
+                if value.abs() > merged.abs() {
+                    value
+                } else {
+                    merged
+                }
+            }),
+            MergePolicy::Sum => {
+                let sum = values.sum::<f32>();
+                match input {
+                    GamepadInput::Button(_) => sum.clamp(0.0, 1.0),
+                    GamepadInput::Axis(_) => sum.clamp(-1.0, 1.0),
+                }
+            }
+            MergePolicy::FirstActive => values.find(|value| *value != 0.0).unwrap_or(0.0),
+        }
+    }
+}
+
+/// Merges input from two or more gamepads into a single logical gamepad.
+///
+/// Spawn this component on a new entity to create the group. The group entity is announced as a connected
+/// gamepad, and the member gamepads are disconnected while the group exists. Removing the component or
+/// despawning the group entity restores the members. A member whose device disconnects and reconnects,
+/// kept with [`crate::DisconnectPolicy::KeepMarked`], rejoins the group instead of connecting on its own.
+///
+/// ```ignore
+/// commands.spawn(
+///     GamepadGroup::new([player, copilot])
+///         .with_input_policy(GamepadButton::South, MergePolicy::FirstActive),
+/// );
+/// ```
+#[derive(Component, Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadGroup {
+    members: Vec<Entity>,
+    policy: MergePolicy,
+    #[cfg_attr(feature = "serialize", serde(with = "crate::codec::input_map"))]
+    overrides: HashMap<GamepadInput, MergePolicy>,
+}
+
+impl GamepadGroup {
+    pub fn new(members: impl IntoIterator<Item = Entity>) -> Self {
+        Self {
+            members: members.into_iter().collect(),
+            policy: MergePolicy::default(),
+            overrides: HashMap::default(),
+        }
+    }
+
+    /// Set the merge policy for all elements without an explicit policy
+    pub fn with_policy(mut self, policy: MergePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the merge policy of a single button or axis
+    pub fn with_input_policy(
+        mut self,
+        input: impl Into<GamepadInput>,
+        policy: MergePolicy,
+    ) -> Self {
+        self.overrides.insert(input.into(), policy);
+        self
+    }
+
+    /// Member gamepad entities, in priority order for [`MergePolicy::FirstActive`]
+    pub fn members(&self) -> &[Entity] {
+        &self.members
+    }
+
+    /// The merge policy applied to a button or axis
+    pub fn policy(&self, input: impl Into<GamepadInput>) -> MergePolicy {
+        self.overrides
+            .get(&input.into())
+            .copied()
+            .unwrap_or(self.policy)
+    }
+}
+
+/// Merge state of a [`GamepadGroup`] kept in [`GamepadPlatformHandler`]
+#[derive(Default)]
+pub(crate) struct GroupState {
+    /// Devices routed to the group, in group order, with the entity they were routed to before joining
+    routes: Vec<(GamepadId, Entity)>,
+
+    /// Last value of each element from each device
+    values: HashMap<(GamepadId, GamepadInput), f32>,
+}
+
+impl GroupState {
+    /// Record a new value from a member device, and return the merged value of the element
+    pub(crate) fn update(
+        &mut self,
+        group: &GamepadGroup,
+        id: GamepadId,
+        input: GamepadInput,
+        value: f32,
+    ) -> f32 {
+        self.values.insert((id, input), value);
+
+        let values = self
+            .routes
+            .iter()
+            .map(|(id, _)| self.values.get(&(*id, input)).copied().unwrap_or(0.0));
+
+        group.policy(input).merge(input, values)
+    }
+
+    /// Forget a device which disconnected
+    pub(crate) fn remove_device(&mut self, id: GamepadId) {
+        self.routes.retain(|(route, _)| *route != id);
+        self.values.retain(|(route, _), _| *route != id);
+    }
+
+    /// Route a device of a member to the group again when it reconnects, keeping the group order
+    pub(crate) fn reconnect(&mut self, group: &GamepadGroup, id: GamepadId, member: Entity) {
+        self.routes.push((id, member));
+        self.routes.sort_by_key(|(id, member)| {
+            let order = group.members().iter().position(|entity| entity == member);
+            (order, *id)
+        });
+    }
+}
+
+/// Connection announced for a group
+pub(crate) fn group_connection() -> GamepadConnection {
+    GamepadConnection::Connected {
+        name: String::from("Gamepad Group"),
+        vendor_id: None,
+        product_id: None,
+    }
+}
+
+/// Route member devices to newly created groups, and restore them when a group is removed
+pub(crate) fn group_system(
+    mut handler: ResMut<GamepadPlatformHandler>,
+    added: Query<(Entity, &GamepadGroup), Added<GamepadGroup>>,
+    mut removed: RemovedComponents<GamepadGroup>,
+    entities: Query<Entity>,
+    mut connection_writer: EventWriter<GamepadConnectionEvent>,
+) {
+    for entity in removed.read() {
+        let Some(state) = handler.groups.remove(&entity) else {
+            continue;
+        };
+
+        for (id, member) in state.routes {
+            handler.index.insert(id, member);
+
+            if let Some(device) = handler
+                .devices
+                .values()
+                .find(|device| device.entity == member)
+            {
+                connection_writer.send(GamepadConnectionEvent {
+                    gamepad: member,
+                    connection: device.connection.clone(),
+                });
+            }
+        }
+
+        if entities.contains(entity) {
+            connection_writer.send(GamepadConnectionEvent {
+                gamepad: entity,
+                connection: GamepadConnection::Disconnected,
+            });
+        }
+    }
+
+    for (entity, group) in added.iter() {
+        let mut state = GroupState::default();
+
+        for member in group.members() {
+            let mut ids = handler
+                .index
+                .iter()
+                .filter(|(_, target)| *target == member)
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+
+            if ids.is_empty() {
+                bevy_log::warn!(?member, "Gamepad group member is not a connected gamepad");
+                continue;
+            }
+
+            ids.sort();
+            state.routes.extend(ids.into_iter().map(|id| (id, *member)));
+
+            connection_writer.send(GamepadConnectionEvent {
+                gamepad: *member,
+                connection: GamepadConnection::Disconnected,
+            });
+        }
+
+        for (id, _) in &state.routes {
+            handler.index.insert(*id, entity);
+        }
+
+        handler.groups.insert(entity, state);
+
+        connection_writer.send(GamepadConnectionEvent {
+            gamepad: entity,
+            connection: group_connection(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_input::gamepad::{GamepadAxis, GamepadButton};
+
+    use super::*;
+
+    const SOUTH: GamepadInput = GamepadInput::Button(GamepadButton::South);
+    const STICK: GamepadInput = GamepadInput::Axis(GamepadAxis::LeftStickX);
+
+    #[test]
+    fn max_magnitude() {
+        assert_eq!(
+            MergePolicy::MaxMagnitude.merge(STICK, [0.3, -0.8, 0.5]),
+            -0.8
+        );
+        assert_eq!(MergePolicy::MaxMagnitude.merge(SOUTH, [0.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn sum_is_clamped_to_the_element_range() {
+        assert_eq!(MergePolicy::Sum.merge(STICK, [0.25, 0.5]), 0.75);
+        assert_eq!(MergePolicy::Sum.merge(STICK, [-0.75, -0.5]), -1.0);
+        assert_eq!(MergePolicy::Sum.merge(SOUTH, [1.0, 1.0]), 1.0);
+        assert_eq!(MergePolicy::Sum.merge(SOUTH, [-0.5, 0.25]), 0.0);
+    }
+
+    #[test]
+    fn first_active_in_group_order() {
+        assert_eq!(MergePolicy::FirstActive.merge(STICK, [0.0, 0.4, 1.0]), 0.4);
+        assert_eq!(MergePolicy::FirstActive.merge(STICK, [0.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn members_merged_per_input_policy() {
+        let group = GamepadGroup::new([Entity::PLACEHOLDER; 2])
+            .with_policy(MergePolicy::Sum)
+            .with_input_policy(GamepadButton::South, MergePolicy::FirstActive);
+        let mut state = GroupState {
+            routes: vec![(0, Entity::PLACEHOLDER), (1, Entity::PLACEHOLDER)],
+            ..Default::default()
+        };
+
+        assert_eq!(state.update(&group, 0, STICK, 0.25), 0.25);
+        assert_eq!(state.update(&group, 1, STICK, 0.5), 0.75);
+        assert_eq!(state.update(&group, 1, SOUTH, 0.5), 0.5);
+        assert_eq!(state.update(&group, 0, SOUTH, 1.0), 1.0);
+
+        // A removed device no longer contributes
+        state.remove_device(0);
+        assert_eq!(state.update(&group, 1, STICK, 0.5), 0.5);
+    }
+
+    #[test]
+    fn reconnect_keeps_group_order() {
+        let [first, second] = [Entity::from_raw(1), Entity::from_raw(2)];
+        let group = GamepadGroup::new([first, second]).with_policy(MergePolicy::FirstActive);
+        let mut state = GroupState {
+            routes: vec![(0, first), (1, second)],
+            ..Default::default()
+        };
+
+        state.remove_device(0);
+        state.reconnect(&group, 0, first);
+        assert_eq!(state.routes, vec![(0, first), (1, second)]);
+
+        // The first member takes priority again
+        assert_eq!(state.update(&group, 1, STICK, 0.5), 0.5);
+        assert_eq!(state.update(&group, 0, STICK, -0.25), -0.25);
+    }
+}
@@ -0,0 +1,314 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::{GamepadButton, GamepadInput};
+use bevy_utils::HashMap;
+
+use crate::timestamp::InputTimestamp;
+
+/// Value at which a button counts as pressed, matching the default press threshold of `bevy_input`
+pub(crate) const PRESS_THRESHOLD: f32 = 0.75;
+
+/// One change of a button or axis recorded in a [`GamepadInputHistory`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputTransition {
+    pub input: GamepadInput,
+
+    /// Value after the change
+    pub value: f32,
+
+    /// Value before the change, or 0 for the first change of the element
+    pub previous: f32,
+
+    pub timestamp: InputTimestamp,
+}
+
+impl InputTransition {
+    /// The change pressed a button
+    pub fn is_press(&self) -> bool {
+        matches!(self.input, GamepadInput::Button(_))
+            && self.previous < PRESS_THRESHOLD
+            && self.value >= PRESS_THRESHOLD
+    }
+
+    /// The change released a button
+    pub fn is_release(&self) -> bool {
+        matches!(self.input, GamepadInput::Button(_))
+            && self.previous >= PRESS_THRESHOLD
+            && self.value < PRESS_THRESHOLD
+    }
+}
+
+/// Ring buffer of every button and axis change of a gamepad, including changes within a single frame.
+///
+/// Insert this component on a gamepad entity to start recording. The oldest transitions are dropped
+/// once the buffer reaches its capacity.
+///
+/// ```ignore
+/// commands.entity(gamepad).insert(GamepadInputHistory::new(128));
+///
+/// fn special_move(histories: Query<&GamepadInputHistory>) {
+///     for history in histories.iter() {
+///         if history.pressed_within(GamepadButton::South, Duration::from_millis(100)) {
+///             // ...
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct GamepadInputHistory {
+    transitions: VecDeque<InputTransition>,
+    capacity: usize,
+
+    /// Last value of each element, to fill [`InputTransition::previous`]
+    values: HashMap<GamepadInput, f32>,
+
+    /// Number of transitions ever pushed
+    pushed: usize,
+
+    /// Value of `pushed` at the start of the current frame
+    frame_start: usize,
+}
+
+impl Default for GamepadInputHistory {
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+impl GamepadInputHistory {
+    /// Create a history holding up to `capacity` transitions
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            transitions: VecDeque::with_capacity(capacity),
+            capacity,
+            values: HashMap::default(),
+            pushed: 0,
+            frame_start: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// All retained transitions, oldest first
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &InputTransition> {
+        self.transitions.iter()
+    }
+
+    /// Transitions received since the start of the current frame, oldest first
+    pub fn since_last_frame(&self) -> impl DoubleEndedIterator<Item = &InputTransition> {
+        let oldest = self.pushed - self.transitions.len();
+        self.transitions
+            .iter()
+            .skip(self.frame_start.saturating_sub(oldest))
+    }
+
+    /// Retained transitions of one button or axis, oldest first
+    pub fn transitions(
+        &self,
+        input: impl Into<GamepadInput>,
+    ) -> impl DoubleEndedIterator<Item = &InputTransition> {
+        let input = input.into();
+        self.transitions
+            .iter()
+            .filter(move |transition| transition.input == input)
+    }
+
+    /// Transitions captured within `window` of now, oldest first
+    pub fn within(&self, window: Duration) -> impl DoubleEndedIterator<Item = &InputTransition> {
+        self.within_of(InputTimestamp::now(), window)
+    }
+
+    /// The button was pressed within `window` of now, even if it has been released since
+    pub fn pressed_within(&self, button: GamepadButton, window: Duration) -> bool {
+        self.within(window).any(|transition| {
+            transition.input == GamepadInput::Button(button) && transition.is_press()
+        })
+    }
+
+    /// The button was released within `window` of now, even if it has been pressed again since
+    pub fn released_within(&self, button: GamepadButton, window: Duration) -> bool {
+        self.within(window).any(|transition| {
+            transition.input == GamepadInput::Button(button) && transition.is_release()
+        })
+    }
+
+    /// Transitions captured within `window` before `now`
+    fn within_of(
+        &self,
+        now: InputTimestamp,
+        window: Duration,
+    ) -> impl DoubleEndedIterator<Item = &InputTransition> {
+        self.transitions.iter().filter(move |transition| {
+            now.captured()
+                .saturating_sub(transition.timestamp.captured())
+                <= window
+        })
+    }
+
+    /// Drop all transitions
+    pub fn clear(&mut self) {
+        self.transitions.clear();
+        self.frame_start = self.pushed;
+    }
+
+    pub(crate) fn push(&mut self, input: GamepadInput, value: f32, timestamp: InputTimestamp) {
+        let previous = self.values.insert(input, value).unwrap_or(0.0);
+
+        if self.transitions.len() >= self.capacity {
+            self.transitions.pop_front();
+        }
+        if self.capacity > 0 {
+            self.transitions.push_back(InputTransition {
+                input,
+                value,
+                previous,
+                timestamp,
+            });
+        }
+        self.pushed += 1;
+    }
+
+    /// Start a new frame for [`Self::since_last_frame`]
+    pub(crate) fn begin_frame(&mut self) {
+        self.frame_start = self.pushed;
+    }
+}
+
+/// Start a new frame in every history, before any input of the frame is emitted
+pub(crate) fn begin_frame_system(mut histories: Query<&mut GamepadInputHistory>) {
+    for mut history in histories.iter_mut() {
+        history.bypass_change_detection().begin_frame();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_input::gamepad::GamepadAxis;
+
+    use super::*;
+
+    const SOUTH: GamepadInput = GamepadInput::Button(GamepadButton::South);
+
+    fn at(millis: u64) -> InputTimestamp {
+        InputTimestamp::from_captured(Duration::from_millis(millis))
+    }
+
+    fn pressed(history: &GamepadInputHistory, now: u64, window: u64) -> bool {
+        history
+            .within_of(at(now), Duration::from_millis(window))
+            .any(|transition| transition.input == SOUTH && transition.is_press())
+    }
+
+    fn values(transitions: impl Iterator<Item = InputTransition>) -> Vec<f32> {
+        transitions.map(|transition| transition.value).collect()
+    }
+
+    #[test]
+    fn press_and_release() {
+        let mut history = GamepadInputHistory::new(8);
+        history.push(SOUTH, 0.5, at(0));
+        history.push(SOUTH, 1.0, at(10));
+        history.push(SOUTH, 0.9, at(20));
+        history.push(SOUTH, 0.0, at(30));
+        history.push(GamepadInput::Axis(GamepadAxis::LeftStickX), 1.0, at(40));
+
+        let transitions: Vec<_> = history.iter().collect();
+        assert_eq!(transitions[1].previous, 0.5);
+        assert!(!transitions[0].is_press());
+        assert!(transitions[1].is_press());
+        assert!(!transitions[2].is_press() && !transitions[2].is_release());
+        assert!(transitions[3].is_release());
+        // Axes are never pressed
+        assert!(!transitions[4].is_press());
+        assert_eq!(history.transitions(GamepadButton::South).count(), 4);
+    }
+
+    #[test]
+    fn pressed_within_window() {
+        let mut history = GamepadInputHistory::new(8);
+        history.push(SOUTH, 1.0, at(100));
+        history.push(SOUTH, 0.0, at(120));
+
+        // The window includes its boundary, and a release since does not hide the press
+        assert!(pressed(&history, 150, 50));
+        assert!(!pressed(&history, 150, 49));
+        assert!(pressed(&history, 100, 0));
+        assert!(history.pressed_within(GamepadButton::South, Duration::MAX));
+        assert!(history.released_within(GamepadButton::South, Duration::MAX));
+        assert!(!history.released_within(GamepadButton::East, Duration::MAX));
+        assert_eq!(
+            history
+                .within_of(at(150), Duration::from_millis(30))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn oldest_dropped_at_capacity() {
+        let mut history = GamepadInputHistory::new(3);
+        for (time, value) in [0.1, 0.2, 0.3, 0.4, 0.5].into_iter().enumerate() {
+            history.push(SOUTH, value, at(time as u64));
+        }
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(values(history.iter().copied()), vec![0.3, 0.4, 0.5]);
+        // Previous values are kept for dropped transitions
+        assert_eq!(history.iter().next().unwrap().previous, 0.2);
+
+        let mut history = GamepadInputHistory::new(0);
+        history.push(SOUTH, 1.0, at(0));
+        assert!(history.is_empty());
+        assert_eq!(history.since_last_frame().count(), 0);
+    }
+
+    #[test]
+    fn since_last_frame() {
+        let mut history = GamepadInputHistory::new(8);
+        history.push(SOUTH, 0.1, at(0));
+        history.push(SOUTH, 0.2, at(1));
+        history.begin_frame();
+        history.push(SOUTH, 0.3, at(2));
+        history.push(SOUTH, 0.4, at(3));
+        assert_eq!(values(history.since_last_frame().copied()), vec![0.3, 0.4]);
+
+        history.begin_frame();
+        assert_eq!(history.since_last_frame().count(), 0);
+        assert_eq!(history.len(), 4);
+
+        history.push(SOUTH, 0.5, at(4));
+        history.clear();
+        assert_eq!(history.since_last_frame().count(), 0);
+    }
+
+    #[test]
+    fn since_last_frame_wraps_around() {
+        let mut history = GamepadInputHistory::new(2);
+        history.push(SOUTH, 0.1, at(0));
+        history.begin_frame();
+        history.push(SOUTH, 0.2, at(1));
+        assert_eq!(values(history.since_last_frame().copied()), vec![0.2]);
+
+        // The frame has more transitions than the buffer holds, so only the retained ones are returned
+        for (time, value) in [0.3, 0.4, 0.5].into_iter().enumerate() {
+            history.push(SOUTH, value, at(time as u64 + 2));
+        }
+        assert_eq!(values(history.since_last_frame().copied()), vec![0.4, 0.5]);
+
+        history.begin_frame();
+        history.push(SOUTH, 0.6, at(5));
+        assert_eq!(values(history.since_last_frame().copied()), vec![0.6]);
+        assert_eq!(values(history.iter().copied()), vec![0.5, 0.6]);
+    }
+}
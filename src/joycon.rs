@@ -0,0 +1,287 @@
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::{GamepadAxis, GamepadButton, GamepadConnection, GamepadConnectionEvent};
+
+use crate::{
+    GamepadGlyphSet, GamepadId, GamepadPlatformHandler,
+    glyph::ButtonLabels,
+    profile::{ButtonChange, FaceLayout, GamepadChange, JoyConSide},
+};
+
+/// Combine a left and a right single Joy-Con into one logical gamepad.
+///
+/// Input from both Joy-Cons is routed to the `left` gamepad entity in the upright orientation
+/// with the layout of a Switch Pro Controller, and the `right` gamepad entity is disconnected.
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct CombineJoyCons {
+    pub left: Entity,
+    pub right: Entity,
+}
+
+/// Split a gamepad previously combined with [`CombineJoyCons`] back into two sideways Joy-Cons
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SplitJoyCons {
+    pub gamepad: Entity,
+}
+
+/// Label table used for a single Joy-Con held sideways
+pub(crate) fn sideways_labels(side: JoyConSide) -> ButtonLabels {
+    match side {
+        JoyConSide::Left => ButtonLabels::JOY_CON_LEFT_SIDEWAYS,
+        JoyConSide::Right => ButtonLabels::JOY_CON_RIGHT_SIDEWAYS,
+    }
+}
+
+/// Rotate a change from a single Joy-Con profile, which maps the Joy-Con upright as half of a pair,
+/// to the Joy-Con held sideways with the rail facing away from the player.
+///
+/// The left Joy-Con is rotated counter clockwise and the right Joy-Con clockwise. In both cases the
+/// stick becomes the left stick, and the directional or face buttons become the face buttons.
+pub(crate) fn sideways(side: JoyConSide, change: GamepadChange) -> GamepadChange {
+    match (side, change) {
+        (
+            JoyConSide::Left,
+            GamepadChange::DualAxis {
+                x_value, y_value, ..
+            },
+        ) => GamepadChange::DualAxis {
+            x_axis: GamepadAxis::LeftStickX,
+            x_value: -y_value,
+            y_axis: GamepadAxis::LeftStickY,
+            y_value: x_value,
+        },
+
+        (
+            JoyConSide::Right,
+            GamepadChange::DualAxis {
+                x_value, y_value, ..
+            },
+        ) => GamepadChange::DualAxis {
+            x_axis: GamepadAxis::LeftStickX,
+            x_value: y_value,
+            y_axis: GamepadAxis::LeftStickY,
+            y_value: -x_value,
+        },
+
+        // The directional buttons of the left Joy-Con become the face buttons
+        (JoyConSide::Left, GamepadChange::DPad(dpad)) => GamepadChange::Buttons(vec![
+            ButtonChange::new(GamepadButton::North, dpad.right()),
+            ButtonChange::new(GamepadButton::South, dpad.left()),
+            ButtonChange::new(GamepadButton::West, dpad.up()),
+            ButtonChange::new(GamepadButton::East, dpad.down()),
+        ]),
+
+        (side, GamepadChange::Button(change)) => {
+            let button = match (side, change.button()) {
+                (JoyConSide::Right, GamepadButton::North) => GamepadButton::East,
+                (JoyConSide::Right, GamepadButton::East) => GamepadButton::South,
+                (JoyConSide::Right, GamepadButton::South) => GamepadButton::West,
+                (JoyConSide::Right, GamepadButton::West) => GamepadButton::North,
+                (JoyConSide::Right, GamepadButton::RightThumb) => GamepadButton::LeftThumb,
+                (JoyConSide::Left, GamepadButton::Select) => GamepadButton::Start,
+                (_, button) => button,
+            };
+            GamepadChange::Button(ButtonChange::new(button, change.value()))
+        }
+
+        (_, change) => change,
+    }
+}
+
+/// Find the [`GamepadId`] of the device which was spawned as `entity`
+fn find_device(handler: &GamepadPlatformHandler, entity: Entity) -> Option<GamepadId> {
+    handler
+        .devices
+        .iter()
+        .find(|(_, device)| device.entity == entity)
+        .map(|(id, _)| *id)
+}
+
+/// Handle [`CombineJoyCons`] and [`SplitJoyCons`] requests by rerouting the gamepad index
+pub(crate) fn joy_con_system(
+    mut commands: Commands,
+    mut handler: ResMut<GamepadPlatformHandler>,
+    mut combine_events: EventReader<CombineJoyCons>,
+    mut split_events: EventReader<SplitJoyCons>,
+    mut connection_writer: EventWriter<GamepadConnectionEvent>,
+) {
+    for event in combine_events.read() {
+        let (Some(left_id), Some(right_id)) = (
+            find_device(&handler, event.left),
+            find_device(&handler, event.right),
+        ) else {
+            bevy_log::warn!(?event, "Joy-Con gamepad not found");
+            continue;
+        };
+
+        let joy_con = |id| {
+            handler
+                .devices
+                .get(&id)
+                .and_then(|device| device.info.joy_con())
+        };
+        let sides = (joy_con(left_id), joy_con(right_id));
+        if sides != (Some(JoyConSide::Left), Some(JoyConSide::Right)) {
+            bevy_log::warn!(
+                ?event,
+                ?sides,
+                "Can only combine a left and a right Joy-Con"
+            );
+            continue;
+        }
+
+        handler.index.insert(right_id, event.left);
+        for id in [left_id, right_id] {
+            if let Some(device) = handler.devices.get_mut(&id) {
+                device.sideways = false;
+            }
+        }
+
+        commands.entity(event.left).insert(GamepadGlyphSet::new(
+            ButtonLabels::NINTENDO,
+            FaceLayout::Nintendo,
+        ));
+
+        connection_writer.send(GamepadConnectionEvent {
+            gamepad: event.right,
+            connection: GamepadConnection::Disconnected,
+        });
+        connection_writer.send(GamepadConnectionEvent {
+            gamepad: event.left,
+            connection: GamepadConnection::Connected {
+                name: format!("Joy-Con (L/R) {left_id}"),
+                vendor_id: None,
+                product_id: None,
+            },
+        });
+    }
+
+    for event in split_events.read() {
+        let Some(left_id) = find_device(&handler, event.gamepad) else {
+            bevy_log::warn!(?event, "Joy-Con gamepad not found");
+            continue;
+        };
+
+        // Any other device routed to this gamepad is the paired right Joy-Con
+        let Some(right_id) = handler
+            .index
+            .iter()
+            .find(|(id, entity)| **id != left_id && **entity == event.gamepad)
+            .map(|(id, _)| *id)
+        else {
+            bevy_log::warn!(?event, "Gamepad is not a combined Joy-Con pair");
+            continue;
+        };
+
+        for id in [left_id, right_id] {
+            let Some(device) = handler.devices.get_mut(&id) else {
+                continue;
+            };
+            device.sideways = true;
+
+            let entity = device.entity;
+            let connection = device.connection.clone();
+            if let Some(side) = device.info.joy_con() {
+                commands.entity(entity).insert(GamepadGlyphSet::new(
+                    sideways_labels(side),
+                    FaceLayout::Standard,
+                ));
+            }
+
+            handler.index.insert(id, entity);
+            connection_writer.send(GamepadConnectionEvent {
+                gamepad: entity,
+                connection,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::DPadChange;
+
+    fn stick(x_value: f32, y_value: f32) -> GamepadChange {
+        GamepadChange::DualAxis {
+            x_axis: GamepadAxis::RightStickX,
+            x_value,
+            y_axis: GamepadAxis::RightStickY,
+            y_value,
+        }
+    }
+
+    fn left_stick(x_value: f32, y_value: f32) -> GamepadChange {
+        GamepadChange::DualAxis {
+            x_axis: GamepadAxis::LeftStickX,
+            x_value,
+            y_axis: GamepadAxis::LeftStickY,
+            y_value,
+        }
+    }
+
+    fn button(button: GamepadButton) -> GamepadChange {
+        GamepadChange::Button(ButtonChange::new(button, 1.0))
+    }
+
+    #[test]
+    fn left_stick_rotates_counter_clockwise() {
+        // Pushed towards the rail, up when upright, is left when held sideways
+        assert_eq!(
+            sideways(JoyConSide::Left, stick(0.0, 1.0)),
+            left_stick(-1.0, 0.0)
+        );
+        assert_eq!(
+            sideways(JoyConSide::Left, stick(1.0, 0.0)),
+            left_stick(0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn right_stick_rotates_clockwise() {
+        assert_eq!(
+            sideways(JoyConSide::Right, stick(0.0, 1.0)),
+            left_stick(1.0, 0.0)
+        );
+        assert_eq!(
+            sideways(JoyConSide::Right, stick(1.0, 0.0)),
+            left_stick(0.0, -1.0)
+        );
+    }
+
+    #[test]
+    fn left_dpad_becomes_face_buttons() {
+        let change = sideways(
+            JoyConSide::Left,
+            GamepadChange::DPad(DPadChange::new(1.0, 0.0, 0.0, 0.0)),
+        );
+        assert_eq!(
+            change,
+            GamepadChange::Buttons(vec![
+                ButtonChange::new(GamepadButton::North, 0.0),
+                ButtonChange::new(GamepadButton::South, 0.0),
+                ButtonChange::new(GamepadButton::West, 1.0),
+                ButtonChange::new(GamepadButton::East, 0.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn right_face_buttons_rotate_clockwise() {
+        for (upright, held) in [
+            (GamepadButton::North, GamepadButton::East),
+            (GamepadButton::East, GamepadButton::South),
+            (GamepadButton::South, GamepadButton::West),
+            (GamepadButton::West, GamepadButton::North),
+            (GamepadButton::RightThumb, GamepadButton::LeftThumb),
+            (GamepadButton::Start, GamepadButton::Start),
+        ] {
+            assert_eq!(sideways(JoyConSide::Right, button(upright)), button(held));
+        }
+        assert_eq!(
+            sideways(JoyConSide::Left, button(GamepadButton::Select)),
+            button(GamepadButton::Start)
+        );
+    }
+}
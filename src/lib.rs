@@ -1,153 +1,694 @@
 use bevy_app::prelude::*;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_input::gamepad::{
-    GamepadButton, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
+    GamepadConnection, GamepadConnectionEvent, GamepadInput, RawGamepadAxisChangedEvent,
     RawGamepadButtonChangedEvent, RawGamepadEvent,
 };
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 use crossbeam::channel::{Receiver, unbounded};
-use platform::{AppleGameControllerPlatform, GamepadPlatformEvent, Platform as _};
+#[cfg(target_vendor = "apple")]
+use platform::AppleGameControllerPlatform;
+use platform::{Platform, PlatformDriver, ReplayPlatform, VirtualPlatform};
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 
+mod channel;
+#[cfg(feature = "serialize")]
+pub mod codec;
+mod device;
+mod diagnostics;
+mod digital;
+mod disconnect;
+mod discovery;
+mod dpad;
 mod error;
+mod fixed;
+mod focus;
+mod glyph;
+mod group;
+mod history;
+mod joycon;
 mod platform;
 mod profile;
+mod replay;
+mod resync;
+pub mod rollback;
+mod settings;
+mod split;
+mod timestamp;
+
+pub use channel::{EventSender, GamepadChannelStats};
+pub use device::GamepadDeviceInfo;
+pub use diagnostics::GamepadDiagnosticsPlugin;
+pub use digital::{AnalogInput, DigitalThreshold, DigitalThresholds};
+pub use disconnect::{DisconnectPolicy, Disconnected};
+pub use discovery::{
+    DiscoveryEnd, DiscoveryFinished, DiscoveryStarted, GamepadDiscovery, GamepadDiscoveryStatus,
+};
+pub use dpad::DPadAsAxis;
+pub use error::{GamepadError, GamepadErrorEvent};
+pub use fixed::FixedGamepadInput;
+pub use focus::FocusPolicy;
+pub use glyph::{ButtonLabel, ButtonLabels, GamepadGlyphSet};
+pub use group::{GamepadGroup, MergePolicy};
+pub use history::{GamepadInputHistory, InputTransition};
+pub use joycon::{CombineJoyCons, SplitJoyCons};
+pub use platform::{GamepadPlatformEvent, VirtualGamepads};
+pub use profile::{
+    ButtonChange, DPadChange, FaceButtonConvention, FaceLayout, GamepadChange, JoyConSide,
+    TriggerReporting,
+};
+pub use replay::{GamepadRecorder, GamepadReplay};
+pub use resync::GamepadResync;
+pub use settings::GamepadPluginSettings;
+pub use split::GamepadSplit;
+pub use timestamp::{InputTimestamp, TimestampedGamepadEvent};
 
 pub struct GamepadPlugin;
 
+/// Stages of the gamepad input pipeline in `PreUpdate`. They run in order, before bevy_input's
+/// [`InputSystem`](bevy_input::InputSystem) processes gamepad events, so connections and input
+/// reach the `Gamepad` components in the frame they are sent.
+///
+/// Systems of other crates can be ordered between the stages, for example to read the raw events
+/// before bevy_input does:
+///
+/// ```ignore
+/// app.add_systems(
+///     PreUpdate,
+///     inspect_raw_events
+///         .after(GamepadPluginSystems::Platform)
+///         .before(bevy_input::InputSystem),
+/// );
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadPluginSystems {
+    /// Apply Joy-Con, group and split commands and the focus policy
+    Prepare,
+
+    /// Drain the channel from the platform driver, and send [`RawGamepadEvent`],
+    /// [`TimestampedGamepadEvent`] and [`GamepadConnectionEvent`] for bevy_input to process
+    Platform,
+
+    /// Update the components of gamepad entities from the state of their devices
+    Device,
+}
+
 #[derive(Resource)]
 struct GamepadPlatformHandler {
     /// Receive channel to receive events from platform drivers
     rx: Receiver<GamepadPlatformEvent>,
 
+    /// Number of input events in the channel, limited to the channel capacity by the sender
+    pending: Arc<AtomicUsize>,
+
+    /// Number of input events dropped by platform drivers because the channel capacity was reached
+    dropped: Arc<AtomicUsize>,
+
+    /// Number of element changes the profiles of platform drivers have no mapping for
+    unhandled: Arc<AtomicUsize>,
+
     /// Index of gamepad player index to bevy [`Gamepad`] entity
     index: HashMap<GamepadId, Entity>,
+
+    /// State of each connected device, used to apply per device mappings
+    devices: HashMap<GamepadId, GamepadDevice>,
+
+    /// Merge state of each [`GamepadGroup`] entity
+    groups: HashMap<Entity, group::GroupState>,
+
+    /// Routing of each [`GamepadSplit`] entity
+    splits: HashMap<Entity, split::SplitState>,
+
+    /// Input received before the device connected, processed once its `Connected` event arrives
+    orphans: HashMap<GamepadId, Vec<GamepadPlatformEvent>>,
+
+    /// Ids of devices which disconnected, whose input is dropped until they connect again
+    disconnected: HashSet<GamepadId>,
+
+    /// Input values sent to the game and reported by devices, to apply the [`FocusPolicy`]
+    focus: focus::FocusState,
+
+    /// State of devices read for a [`GamepadResync`], processed before the events of the channel
+    resync: Vec<GamepadPlatformEvent>,
+}
+
+impl GamepadPlatformHandler {
+    /// Input of some device is routed to the entity
+    fn is_routed(&self, entity: Entity) -> bool {
+        self.index.values().any(|target| *target == entity)
+    }
+}
+
+/// Plugin side state of a connected device
+struct GamepadDevice {
+    /// Entity spawned for the device. Input may be routed to a different entity through [`GamepadPlatformHandler::index`]
+    entity: Entity,
+
+    info: GamepadDeviceInfo,
+
+    /// Connection reported by the platform, to announce the entity again after it was rerouted
+    connection: GamepadConnection,
+
+    /// Single Joy-Con held sideways
+    sideways: bool,
+
+    /// Last value of each input reported by D-pad changes, which report every direction. Inputs start released.
+    dpad: HashMap<GamepadInput, f32>,
 }
 
 impl Plugin for GamepadPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(PreUpdate, platform_system);
+        timestamp::start_epoch();
+        app.init_resource::<GamepadPluginSettings>();
+        app.init_resource::<GamepadChannelStats>();
+        app.init_resource::<GamepadDiscoveryStatus>();
+        app.add_event::<CombineJoyCons>();
+        app.add_event::<SplitJoyCons>();
+        app.add_event::<TimestampedGamepadEvent>();
+        app.add_event::<GamepadErrorEvent>();
+        app.add_event::<GamepadDiscovery>();
+        app.add_event::<DiscoveryStarted>();
+        app.add_event::<DiscoveryFinished>();
+        app.add_event::<GamepadResync>();
+        app.configure_sets(
+            PreUpdate,
+            (
+                GamepadPluginSystems::Prepare,
+                GamepadPluginSystems::Platform,
+                GamepadPluginSystems::Device,
+            )
+                .chain()
+                // Connections and input must reach bevy_input in the frame they are sent
+                .before(bevy_input::InputSystem),
+        );
+        app.add_systems(
+            PreUpdate,
+            (
+                (
+                    joycon::joy_con_system,
+                    group::group_system,
+                    split::split_system,
+                    history::begin_frame_system,
+                    focus::focus_system,
+                )
+                    .chain()
+                    .in_set(GamepadPluginSystems::Prepare),
+                platform_system.in_set(GamepadPluginSystems::Platform),
+                device::face_button_system.in_set(GamepadPluginSystems::Device),
+            ),
+        );
+        app.add_systems(FixedPreUpdate, fixed::fixed_input_system);
+        app.add_systems(PostUpdate, disconnect::despawn_system);
 
+        // The capacity is enforced by the sender for input only, so the channel itself is unbounded
+        let capacity = app
+            .world()
+            .resource::<GamepadPluginSettings>()
+            .channel_capacity;
         let (tx, rx) = unbounded();
+        let pending = Arc::new(AtomicUsize::new(0));
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let unhandled = Arc::new(AtomicUsize::new(0));
+        let tx = EventSender::new(
+            tx,
+            capacity,
+            pending.clone(),
+            dropped.clone(),
+            unhandled.clone(),
+        );
 
         app.insert_resource(GamepadPlatformHandler {
             rx,
+            pending,
+            dropped,
+            unhandled,
             index: HashMap::default(),
+            devices: HashMap::default(),
+            groups: HashMap::default(),
+            splits: HashMap::default(),
+            orphans: HashMap::default(),
+            disconnected: HashSet::default(),
+            focus: focus::FocusState::default(),
+            resync: Vec::new(),
         });
 
-        AppleGameControllerPlatform::new(app, tx).unwrap();
+        // Play back a recording or drive virtual gamepads instead of reading devices if requested
+        if app.world().contains_resource::<GamepadReplay>() {
+            start_platform::<ReplayPlatform>(app, tx);
+            return;
+        }
+
+        if app.world().contains_resource::<VirtualGamepads>() {
+            start_platform::<VirtualPlatform>(app, tx);
+            return;
+        }
+
+        #[cfg(target_vendor = "apple")]
+        start_platform::<AppleGameControllerPlatform>(app, tx);
+
+        #[cfg(not(target_vendor = "apple"))]
+        bevy_log::warn!("No gamepad platform driver available for this target");
     }
 }
 
-pub(crate) type GamepadId = usize;
+/// Start a platform driver, owned by the app until it exits. A failure is reported through the
+/// channel, so it surfaces as a [`GamepadErrorEvent`] in the first frame instead of panicking in
+/// the plugin build.
+fn start_platform<P: Platform>(app: &mut App, tx: EventSender) {
+    match P::new(app, tx.clone()) {
+        Ok(driver) => {
+            app.insert_non_send_resource(PlatformDriver::new(driver));
+            app.add_systems(
+                PreUpdate,
+                (
+                    discovery::discovery_system::<P>,
+                    resync::resync_system::<P>.before(platform_system),
+                )
+                    .in_set(GamepadPluginSystems::Platform),
+            );
+            app.add_systems(Last, platform::shutdown_system::<P>);
+        }
+        Err(error) => {
+            if let Err(e) = tx.send(GamepadPlatformEvent::Error(error)) {
+                bevy_log::error!("Failed to send to controller event channel: {e}");
+            }
+        }
+    }
+}
 
+/// Platform identifier of a physical gamepad device
+pub type GamepadId = usize;
+
+#[allow(clippy::too_many_arguments)]
 fn platform_system(
     mut commands: Commands,
     mut handler: ResMut<GamepadPlatformHandler>,
+    settings: Res<GamepadPluginSettings>,
+    conventions: Query<&FaceButtonConvention>,
+    groups: Query<&GamepadGroup>,
+    dpad_axes: Query<(), With<DPadAsAxis>>,
+    disconnected: Query<(Entity, &Disconnected, &GamepadDeviceInfo)>,
+    mut sinks: InputSinks,
+    mut recorder: Option<ResMut<GamepadRecorder>>,
+    mut stats: ResMut<GamepadChannelStats>,
     mut connection_writer: EventWriter<GamepadConnectionEvent>,
-    mut gamepad_events: EventWriter<RawGamepadEvent>,
+    mut error_events: EventWriter<GamepadErrorEvent>,
 ) {
-    while let Ok(event) = handler.rx.try_recv() {
+    // Only drain the events queued at the start of the frame, so a flood of events cannot stall PreUpdate
+    let queued = handler.rx.len();
+    let mut events = std::mem::take(&mut handler.resync);
+    let resynced = events.len();
+    events.extend(handler.rx.try_iter().take(queued));
+    let received = events.len() - resynced;
+    let drained = events[resynced..]
+        .iter()
+        .filter(|event| matches!(event, GamepadPlatformEvent::InputChanged { .. }))
+        .count();
+    handler.pending.fetch_sub(drained, Ordering::Relaxed);
+    let latency = events[resynced..]
+        .iter()
+        .filter_map(|event| match event {
+            GamepadPlatformEvent::InputChanged { timestamp, .. } => Some(timestamp.elapsed()),
+            _ => None,
+        })
+        .max();
+    let coalesced = if settings.coalesce_axes {
+        channel::coalesce_axes(&mut events)
+    } else {
+        0
+    };
+    stats.record_frame(received, coalesced, queued, handler.rx.len(), latency);
+    stats.update_unhandled(handler.unhandled.load(Ordering::Relaxed));
+
+    let dropped = stats.update_dropped(handler.dropped.load(Ordering::Relaxed));
+    if dropped > 0 {
+        bevy_log::warn!(
+            dropped,
+            "Gamepad event channel full, input events were dropped"
+        );
+    }
+
+    // Input of devices which never connected is dropped after a timeout
+    let mut orphaned = 0;
+    for orphans in handler.orphans.values_mut() {
+        let count = orphans.len();
+        orphans.retain(|event| match event {
+            GamepadPlatformEvent::InputChanged { timestamp, .. } => {
+                timestamp.elapsed() <= settings.orphan_timeout
+            }
+            _ => false,
+        });
+        orphaned += count - orphans.len();
+    }
+    handler.orphans.retain(|_, orphans| !orphans.is_empty());
+
+    // Record in the order received, before input of unknown devices is deferred
+    if let Some(recorder) = recorder.as_mut() {
+        for event in &events {
+            if let Err(e) = recorder.record(event) {
+                bevy_log::error!("Failed to record gamepad event: {e}");
+            }
+        }
+    }
+
+    let mut queue = VecDeque::from(events);
+    while let Some(event) = queue.pop_front() {
         match event {
             // Handle any errors sent over the channel from the platform driver
-            GamepadPlatformEvent::Error(gamepad_error) => {
-                bevy_log::error!("{gamepad_error}");
+            GamepadPlatformEvent::Error(error) => {
+                bevy_log::error!("{error}");
+
+                let gamepad = error
+                    .gamepad_id()
+                    .and_then(|id| handler.index.get(&id).copied());
+                error_events.send(GamepadErrorEvent { gamepad, error });
             }
 
-            GamepadPlatformEvent::Connected { id, connection } => {
-                let entity = commands.spawn_empty().id();
-                handler.index.insert(id, entity);
-                connection_writer.send(GamepadConnectionEvent {
-                    gamepad: entity,
-                    connection,
+            GamepadPlatformEvent::Connected {
+                id,
+                connection,
+                info,
+                labels,
+            } => {
+                // Single Joy-Cons start out sideways, until combined with CombineJoyCons
+                let glyphs = match info.joy_con() {
+                    Some(side) => {
+                        GamepadGlyphSet::new(joycon::sideways_labels(side), FaceLayout::Standard)
+                    }
+                    None => GamepadGlyphSet::new(labels, info.face_layout()),
+                };
+
+                // Reuse the entity of the same device if it was kept after disconnecting
+                let reconnected = disconnected
+                    .iter()
+                    .find(|(_, marker, previous)| {
+                        marker.id() == id && previous.profile() == info.profile()
+                    })
+                    .map(|(entity, ..)| entity);
+
+                let entity = match reconnected {
+                    Some(entity) => {
+                        commands
+                            .entity(entity)
+                            .remove::<Disconnected>()
+                            .insert((glyphs, info.clone()));
+                        entity
+                    }
+                    None => commands.spawn((glyphs, info.clone())).id(),
+                };
+                // A reconnected group member is routed to its group again
+                let group = handler.groups.keys().copied().find(|group| {
+                    groups
+                        .get(*group)
+                        .is_ok_and(|group| group.members().contains(&entity))
                 });
+
+                handler.disconnected.remove(&id);
+                let group_connected = group.is_some_and(|group| handler.is_routed(group));
+                handler.index.insert(id, group.unwrap_or(entity));
+                handler.devices.insert(
+                    id,
+                    GamepadDevice {
+                        entity,
+                        sideways: info.joy_con().is_some(),
+                        info,
+                        connection: connection.clone(),
+                        dpad: HashMap::default(),
+                    },
+                );
+
+                // A group member or split gamepad stays disconnected, its group or parts are connected instead
+                match (group, handler.splits.get_mut(&entity)) {
+                    (Some(group), _) => {
+                        if let (Some(state), Ok(members)) =
+                            (handler.groups.get_mut(&group), groups.get(group))
+                        {
+                            state.reconnect(members, id, entity);
+                        }
+                        if !group_connected {
+                            connection_writer.send(GamepadConnectionEvent {
+                                gamepad: group,
+                                connection: group::group_connection(),
+                            });
+                        }
+                    }
+                    (None, Some(split)) => {
+                        split.reconnect(&mut commands, connection, &mut connection_writer);
+                    }
+                    (None, None) => {
+                        connection_writer.send(GamepadConnectionEvent {
+                            gamepad: entity,
+                            connection,
+                        });
+                    }
+                }
+
+                // Input which raced ahead of the connect notification is processed right after it
+                if let Some(orphans) = handler.orphans.remove(&id) {
+                    for event in orphans.into_iter().rev() {
+                        queue.push_front(event);
+                    }
+                }
             }
 
             GamepadPlatformEvent::Disconnected { id } => {
-                if let Some(entity) = handler.index.get(&id) {
-                    connection_writer.send(GamepadConnectionEvent {
-                        gamepad: *entity,
-                        connection: bevy_input::gamepad::GamepadConnection::Disconnected,
-                    });
+                if let Some(orphans) = handler.orphans.remove(&id) {
+                    orphaned += orphans.len();
                 }
-            }
+                handler.disconnected.insert(id);
 
-            GamepadPlatformEvent::InputChanged { id, change } => {
-                let Some(gamepad) = handler.index.get(&id) else {
-                    return;
+                // Remove every route of the id, so a different device reusing it gets its own entity
+                for state in handler.groups.values_mut() {
+                    state.remove_device(id);
+                }
+
+                // A group or combined Joy-Con stays connected while another device is routed to it
+                let routed = handler.index.remove(&id);
+                if let Some(entity) = routed.filter(|entity| !handler.is_routed(*entity)) {
+                    // The parts of a split gamepad are connected in its place
+                    let gamepads = match handler.splits.get(&entity) {
+                        Some(split) => split.parts().collect(),
+                        None => vec![entity],
+                    };
+                    for gamepad in gamepads {
+                        handler.focus.forget(gamepad);
+                        connection_writer.send(GamepadConnectionEvent {
+                            gamepad,
+                            connection: GamepadConnection::Disconnected,
+                        });
+                    }
+                }
+
+                let Some(device) = handler.devices.remove(&id) else {
+                    continue;
                 };
 
-                match change {
-                    profile::Changed::Button(button_change) => {
-                        let event = RawGamepadButtonChangedEvent {
-                            gamepad: *gamepad,
-                            button: button_change.button(),
-                            value: button_change.value(),
-                        };
+                // The entity is still in use while another device is routed to it
+                if handler.is_routed(device.entity) {
+                    continue;
+                }
+
+                // Parts of a split gamepad follow the policy of the gamepad, and are restored on reconnect
+                let parts: Vec<_> = match handler.splits.get(&device.entity) {
+                    Some(split) => split.parts().collect(),
+                    None => Vec::new(),
+                };
 
-                        gamepad_events.send(RawGamepadEvent::Button(event));
+                match settings.disconnect_policy {
+                    DisconnectPolicy::Despawn => {
+                        handler.splits.remove(&device.entity);
+                        for entity in parts.into_iter().chain([device.entity]) {
+                            commands.entity(entity).insert(disconnect::Despawn);
+                        }
                     }
+                    DisconnectPolicy::KeepMarked => {
+                        for entity in parts.into_iter().chain([device.entity]) {
+                            commands.entity(entity).insert(Disconnected::new(id));
+                        }
+                    }
+                    DisconnectPolicy::Keep => {}
+                }
+            }
 
-                    profile::Changed::DPad(dpad_change) => {
-                        let up_event = RawGamepadButtonChangedEvent {
-                            gamepad: *gamepad,
-                            button: GamepadButton::DPadUp,
-                            value: dpad_change.up(),
-                        };
-                        let down_event = RawGamepadButtonChangedEvent {
-                            gamepad: *gamepad,
-                            button: GamepadButton::DPadDown,
-                            value: dpad_change.down(),
-                        };
-                        let left_event = RawGamepadButtonChangedEvent {
-                            gamepad: *gamepad,
-                            button: GamepadButton::DPadLeft,
-                            value: dpad_change.left(),
-                        };
-                        let right_event = RawGamepadButtonChangedEvent {
-                            gamepad: *gamepad,
-                            button: GamepadButton::DPadRight,
-                            value: dpad_change.right(),
-                        };
-
-                        gamepad_events.send(RawGamepadEvent::Button(up_event));
-                        gamepad_events.send(RawGamepadEvent::Button(down_event));
-                        gamepad_events.send(RawGamepadEvent::Button(left_event));
-                        gamepad_events.send(RawGamepadEvent::Button(right_event));
+            GamepadPlatformEvent::InputChanged {
+                id,
+                change,
+                timestamp,
+            } => {
+                let Some(gamepad) = handler.index.get(&id).copied() else {
+                    // Input sent before a disconnect is stale, it must not be replayed on reconnect
+                    if handler.disconnected.contains(&id) {
+                        orphaned += 1;
+                        continue;
                     }
 
-                    profile::Changed::DualAxis {
-                        x_axis,
-                        x_value,
-                        y_axis,
-                        y_value,
-                    } => {
-                        let x_event = RawGamepadAxisChangedEvent {
-                            gamepad: *gamepad,
-                            axis: x_axis,
-                            value: x_value,
-                        };
-
-                        let y_event = RawGamepadAxisChangedEvent {
-                            gamepad: *gamepad,
-                            axis: y_axis,
-                            value: y_value,
-                        };
-
-                        gamepad_events.send(RawGamepadEvent::Axis(x_event));
-                        gamepad_events.send(RawGamepadEvent::Axis(y_event));
+                    // Hold on to input of devices which have not connected yet
+                    handler.orphans.entry(id).or_default().push(
+                        GamepadPlatformEvent::InputChanged {
+                            id,
+                            change,
+                            timestamp,
+                        },
+                    );
+                    continue;
+                };
+                stats.record_event(gamepad);
+
+                let (change, face_layout) = match handler.devices.get(&id) {
+                    Some(GamepadDevice {
+                        info,
+                        sideways: true,
+                        ..
+                    }) => match info.joy_con() {
+                        Some(side) => (joycon::sideways(side, change), FaceLayout::Standard),
+                        None => (change, info.face_layout()),
+                    },
+                    Some(device) => (change, device.info.face_layout()),
+                    None => (change, FaceLayout::Standard),
+                };
+
+                // Face buttons of Nintendo layout devices are remapped to the active convention
+                let convention = conventions
+                    .get(gamepad)
+                    .copied()
+                    .unwrap_or(settings.face_buttons);
+
+                // D-pad changes report every direction, only the directions which changed are emitted
+                let filter_unchanged =
+                    matches!(change, GamepadChange::DPad(_) | GamepadChange::Buttons(_));
+                let mut inputs = match change {
+                    GamepadChange::DPad(dpad) if dpad_axes.contains(gamepad) => {
+                        let axes = DPadAsAxis::axes(&dpad);
+                        let mut inputs = GamepadChange::DPad(dpad).into_inputs();
+                        inputs.extend(axes);
+                        inputs
                     }
+                    change => change.into_inputs(),
+                };
+                if let (true, Some(device)) = (filter_unchanged, handler.devices.get_mut(&id)) {
+                    dpad::filter_changed(&mut device.dpad, &mut inputs);
+                }
+
+                // Triggers are reported as buttons, axes or both, whichever the profile mapped them to
+                let inputs = inputs
+                    .into_iter()
+                    .flat_map(|(input, value)| settings.triggers.apply(input, value));
+
+                for (input, value) in inputs {
+                    let input = match input {
+                        GamepadInput::Button(button) => {
+                            GamepadInput::Button(convention.apply(face_layout, button))
+                        }
+                        axis => axis,
+                    };
+
+                    // Merge with the other members if the device is part of a group
+                    let value = match (handler.groups.get_mut(&gamepad), groups.get(gamepad)) {
+                        (Some(state), Ok(group)) => state.update(group, id, input, value),
+                        _ => value,
+                    };
 
-                    profile::Changed::SingleAxis { axis, value } => {
-                        let event = RawGamepadAxisChangedEvent {
-                            gamepad: *gamepad,
-                            axis,
-                            value,
-                        };
+                    // Route to the part the input belongs to if the gamepad is split
+                    let target = match handler.splits.get(&gamepad) {
+                        Some(split) => match split.route(input) {
+                            Some(part) => part,
+                            None => continue,
+                        },
+                        None => gamepad,
+                    };
 
-                        gamepad_events.send(RawGamepadEvent::Axis(event));
+                    // Withheld from the game while unfocused, depending on the focus policy
+                    if handler.focus.update(target, input, value) {
+                        sinks.emit(target, input, value, timestamp);
                     }
                 }
             }
         }
     }
+
+    if orphaned > 0 {
+        bevy_log::warn!(
+            orphaned,
+            "Dropped input of gamepads which were not connected"
+        );
+    }
+    stats.record_orphaned(orphaned);
+}
+
+/// Events and opt-in components on gamepad entities which receive every emitted input
+#[derive(SystemParam)]
+struct InputSinks<'w, 's> {
+    histories: Query<'w, 's, &'static mut GamepadInputHistory>,
+    fixed_inputs: Query<'w, 's, &'static mut FixedGamepadInput>,
+    snapshots: Query<'w, 's, &'static mut rollback::GamepadSnapshot>,
+    thresholds: Query<'w, 's, &'static mut DigitalThresholds>,
+    gamepad_events: EventWriter<'w, RawGamepadEvent>,
+    timestamped_events: EventWriter<'w, TimestampedGamepadEvent>,
+}
+
+impl InputSinks<'_, '_> {
+    /// Send an input change to the game, with the digital buttons it pressed or released
+    fn emit(
+        &mut self,
+        gamepad: Entity,
+        input: GamepadInput,
+        value: f32,
+        timestamp: InputTimestamp,
+    ) {
+        self.send(gamepad, input, value, timestamp);
+
+        let Ok(mut thresholds) = self.thresholds.get_mut(gamepad) else {
+            return;
+        };
+        for (button, pressed) in thresholds.update(input, value) {
+            let value = if pressed { 1.0 } else { 0.0 };
+            self.send(gamepad, button.into(), value, timestamp);
+        }
+    }
+
+    fn send(
+        &mut self,
+        gamepad: Entity,
+        input: GamepadInput,
+        value: f32,
+        timestamp: InputTimestamp,
+    ) {
+        if let Ok(mut history) = self.histories.get_mut(gamepad) {
+            history.push(input, value, timestamp);
+        }
+        if let Ok(mut fixed_input) = self.fixed_inputs.get_mut(gamepad) {
+            fixed_input.push(input, value, timestamp);
+        }
+        if let Ok(mut snapshot) = self.snapshots.get_mut(gamepad) {
+            snapshot.apply(input, value);
+        }
+
+        let event = raw_event(gamepad, input, value);
+        self.timestamped_events.send(TimestampedGamepadEvent {
+            event: event.clone(),
+            timestamp,
+        });
+        self.gamepad_events.send(event);
+    }
+}
+
+fn raw_event(gamepad: Entity, input: GamepadInput, value: f32) -> RawGamepadEvent {
+    match input {
+        GamepadInput::Button(button) => RawGamepadEvent::Button(RawGamepadButtonChangedEvent {
+            gamepad,
+            button,
+            value,
+        }),
+        GamepadInput::Axis(axis) => RawGamepadEvent::Axis(RawGamepadAxisChangedEvent {
+            gamepad,
+            axis,
+            value,
+        }),
+    }
 }
@@ -1,30 +1,116 @@
-use bevy_app::App;
+use bevy_app::{App, AppExit};
+use bevy_ecs::prelude::*;
 use bevy_input::gamepad::GamepadConnection;
-use crossbeam::channel::Sender;
 
-use crate::{GamepadId, error::GamepadError, profile::Changed};
+use crate::{
+    GamepadDeviceInfo, GamepadId, channel::EventSender, error::GamepadError, glyph::ButtonLabels,
+    profile::GamepadChange, timestamp::InputTimestamp,
+};
+#[cfg(target_vendor = "apple")]
 mod apple;
+mod replay;
+mod virtual_gamepads;
 
+#[cfg(target_vendor = "apple")]
 pub use apple::AppleGameControllerPlatform;
+pub use replay::ReplayPlatform;
+pub use virtual_gamepads::{VirtualGamepads, VirtualPlatform};
 
 /// Platform trait abstracts underlying platform gamepad interface
-/// * Currently only supports Apple Game Controller framework in [`AppleGameControllerPlatform`]
-pub trait Platform: Sized {
+/// * Apple Game Controller framework in `AppleGameControllerPlatform`, on Apple targets
+/// * Playback of recorded events in [`ReplayPlatform`], on all targets
+/// * Gamepads driven from code in [`VirtualPlatform`], on all targets
+pub trait Platform: Sized + 'static {
     /// The type of handle to access a gamepad in [`GamepadHandle`]
     type Handle;
 
     /// Initialize a new platform driver. Called during plugin initialization.
     /// The App struct is passed if the platform implementation needs to insert
     /// resources into the World.
-    fn new(app: &mut App, tx: Sender<GamepadPlatformEvent>) -> Result<Self, GamepadError>;
+    fn new(app: &mut App, tx: EventSender) -> Result<Self, GamepadError>;
+
+    /// Start looking for new wireless controllers
+    fn start_discovery(&mut self) -> Result<(), GamepadError> {
+        Err(GamepadError::Platform(
+            "Controller discovery is not supported by this platform driver".into(),
+        ))
+    }
+
+    /// Stop looking for new wireless controllers
+    fn stop_discovery(&mut self) {}
+
+    /// Whether the driver is looking for new wireless controllers
+    fn discovering(&self) -> bool {
+        false
+    }
+
+    /// Current state of every input of a connected device, emitted by the plugin to resync the game.
+    /// Drivers which cannot read the state of a device report no changes.
+    fn snapshot(&mut self, _id: GamepadId) -> Result<Vec<GamepadChange>, GamepadError> {
+        Ok(Vec::new())
+    }
+
+    /// Stop the platform driver, unregistering its callbacks and stopping device discovery.
+    /// Called once, when the app exits or is dropped.
+    fn shutdown(&mut self) {}
+}
+
+/// Owns the platform driver of the app. A non-send resource, as platform handles may only be
+/// used from the main thread.
+pub(crate) struct PlatformDriver<P: Platform> {
+    driver: Option<P>,
+}
+
+impl<P: Platform> PlatformDriver<P> {
+    pub(crate) fn new(driver: P) -> Self {
+        Self {
+            driver: Some(driver),
+        }
+    }
+
+    /// The driver, until it has been shut down
+    pub(crate) fn get_mut(&mut self) -> Option<&mut P> {
+        self.driver.as_mut()
+    }
+
+    fn shutdown(&mut self) {
+        if let Some(mut driver) = self.driver.take() {
+            driver.shutdown();
+        }
+    }
+}
+
+impl<P: Platform> Drop for PlatformDriver<P> {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Shut down the platform driver when the app exits
+pub(crate) fn shutdown_system<P: Platform>(
+    mut exit: EventReader<AppExit>,
+    driver: Option<NonSendMut<PlatformDriver<P>>>,
+) {
+    if exit.read().count() == 0 {
+        return;
+    }
+
+    if let Some(mut driver) = driver {
+        driver.shutdown();
+    }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum GamepadPlatformEvent {
     Error(GamepadError),
     Connected {
         id: GamepadId,
         connection: GamepadConnection,
+        /// Device and profile information of the connected gamepad
+        info: GamepadDeviceInfo,
+        /// Button labels of the profile matched to the connected gamepad
+        labels: ButtonLabels,
     },
 
     Disconnected {
@@ -33,6 +119,8 @@ pub enum GamepadPlatformEvent {
 
     InputChanged {
         id: GamepadId,
-        change: Changed,
+        change: GamepadChange,
+        /// Time the change was captured in the driver callback
+        timestamp: InputTimestamp,
     },
 }
@@ -4,32 +4,73 @@ use bevy_app::App;
 use bevy_input::gamepad::GamepadConnection;
 use bevy_log::{debug, error, info, trace, warn};
 use block2::StackBlock;
-use crossbeam::channel::Sender;
-use objc2::rc::Retained;
-use objc2_foundation::{NSNotification, NSNotificationCenter};
+use objc2::{
+    rc::Retained,
+    runtime::{AnyObject, ProtocolObject},
+};
+use objc2_foundation::{NSNotification, NSNotificationCenter, NSObjectProtocol};
 use objc2_game_controller::{
     GCController, GCControllerDidConnectNotification, GCControllerDidDisconnectNotification,
     GCControllerPlayerIndex, GCDevice, GCDualSenseGamepad, GCDualShockGamepad, GCExtendedGamepad,
     GCMicroGamepad, GCXboxGamepad,
 };
 use profiles::{
-    ApplePlatformProfile, DualSenseProfile, DualShockProfile, GenericProfile, SwitchProfile,
-    XboxProfile,
+    ApplePlatformProfile, DualSenseProfile, DualShockProfile, GenericProfile, JoyConProfile,
+    SwitchProfile, XboxProfile,
+};
+use std::{
+    panic::{AssertUnwindSafe, catch_unwind},
+    ptr::NonNull,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
 };
-use std::{ptr::NonNull, sync::Arc};
 
 use super::{GamepadPlatformEvent, Platform};
-use crate::{GamepadId, error::GamepadError};
+use crate::{
+    GamepadDeviceInfo, GamepadId, JoyConSide,
+    channel::EventSender,
+    error::GamepadError,
+    glyph::ButtonLabels,
+    profile::{GamepadChange, Profile as _},
+    timestamp::InputTimestamp,
+};
+
+/// Vendor names of the 8BitDo controllers with the Nintendo face button layout
+const NINTENDO_LAYOUT_8BITDO: &[&str] = &[
+    "8BitDo SN30 Pro",
+    "8BitDo SN30 Pro+",
+    "8BitDo Lite",
+    "8BitDo Zero 2",
+];
 
 pub struct AppleGameControllerPlatform {
     /// Apple Notification Center
     notification_center: Retained<NSNotificationCenter>,
+
+    /// Observer tokens of the registered notifications, needed to remove them
+    observers: Vec<Retained<AnyObject>>,
+
+    /// Number of the running discovery session, or 0 when not discovering. A restarted session
+    /// replaces the completion handler of the previous one.
+    discovery: Arc<AtomicUsize>,
 }
 
 impl AppleGameControllerPlatform {
-    fn register_notifications(&self, tx: Sender<GamepadPlatformEvent>) -> Result<(), GamepadError> {
-        self.register_connect_notification(tx.clone());
-        self.register_disconnect_notification(tx);
+    fn register_notifications(&mut self, tx: EventSender) -> Result<(), GamepadError> {
+        let observers = [
+            self.register_connect_notification(tx.clone()),
+            self.register_disconnect_notification(tx),
+        ];
+
+        // The tokens are only used as opaque objects to remove the observers
+        self.observers.extend(
+            observers
+                .into_iter()
+                .map(|observer| unsafe { Retained::cast_unchecked(observer) }),
+        );
 
         Ok(())
     }
@@ -59,51 +100,84 @@ impl AppleGameControllerPlatform {
 
     fn init_gamepad(
         id: GamepadId,
-        tx: Sender<GamepadPlatformEvent>,
+        tx: EventSender,
         gamepad: Retained<GCExtendedGamepad>,
-    ) {
+    ) -> Result<(GamepadDeviceInfo, ButtonLabels, Vec<GamepadChange>), GamepadError> {
         // Create a profile for a specific type of connected gamepad.
         // The profiles wrap the GCDualSenseGamepad, GCXboxGamepad etc to handle the mappings
         // back to bevy GamepadButton/GamepadAxis types
-        let profile = Arc::new(Self::get_gamepad_profile(gamepad.clone()));
+        let profile = Arc::new(Self::get_gamepad_profile(id, gamepad.clone())?);
+        let info = GamepadDeviceInfo::from_profile(&**profile);
+        let labels = profile.button_labels();
+
+        // Inputs already held when the gamepad connects never trigger the change handler
+        let snapshot = profile.snapshot(&gamepad);
 
         // Setup a change handler on the gamepad
         unsafe {
             let value_changed = StackBlock::new(
                 move |gamepad: NonNull<GCExtendedGamepad>,
                       event: NonNull<objc2_game_controller::GCControllerElement>| {
-                    let _gamepad = gamepad.as_ref();
-                    let event = event.as_ref();
-
-                    trace!(?event, "Change event");
-
-                    if let Some(change) = profile.element_changed(event) {
-                        tx.send(GamepadPlatformEvent::InputChanged { id, change })
-                            .unwrap();
-                    } else {
-                        warn!(?event, "Unhandled change event in gamepad platform driver");
-                    }
+                    catch_panic(&tx, "value changed", || {
+                        // Capture the time first, before any mapping work
+                        let timestamp = InputTimestamp::now();
+                        let gamepad = gamepad.as_ref();
+                        let event = event.as_ref();
+
+                        trace!(?event, "Change event");
+
+                        // Device time of the event, in seconds of system uptime
+                        let timestamp =
+                            match Duration::try_from_secs_f64(gamepad.lastEventTimestamp()) {
+                                Ok(device) => timestamp.with_device(device),
+                                Err(_) => timestamp,
+                            };
+
+                        if let Some(change) = profile.element_changed(event) {
+                            if let Err(e) = tx.send(GamepadPlatformEvent::InputChanged {
+                                id,
+                                change,
+                                timestamp,
+                            }) {
+                                error!("Failed to send to controller event channel: {e}");
+                            }
+                        } else {
+                            tx.unhandled();
+                            warn!(?event, "Unhandled change event in gamepad platform driver");
+                        }
+                    })
                 },
             );
 
             gamepad.setValueChangedHandler(&*value_changed as *const _ as *mut _);
         }
+
+        Ok((info, labels, snapshot))
     }
 
-    fn get_gamepad_profile(gamepad: Retained<GCExtendedGamepad>) -> Box<dyn ApplePlatformProfile> {
-        match gamepad.downcast::<GCDualSenseGamepad>() {
+    fn get_gamepad_profile(
+        id: GamepadId,
+        gamepad: Retained<GCExtendedGamepad>,
+    ) -> Result<Box<dyn ApplePlatformProfile>, GamepadError> {
+        Ok(match gamepad.downcast::<GCDualSenseGamepad>() {
             Ok(gamepad) => Box::new(DualSenseProfile(gamepad)),
             Err(gamepad) => match gamepad.downcast::<GCDualShockGamepad>() {
                 Ok(gamepad) => Box::new(DualShockProfile(gamepad)),
                 Err(gamepad) => match gamepad.downcast::<GCXboxGamepad>() {
                     Ok(gamepad) => Box::new(XboxProfile(gamepad)),
                     Err(gamepad) => match gamepad.downcast::<GCMicroGamepad>() {
-                        Ok(_) => todo!(),
+                        // The micro gamepad profile of the Siri Remote has too few elements to map
+                        Ok(_) => {
+                            return Err(GamepadError::UnsupportedController {
+                                id: Some(id),
+                                name: String::from("Micro Gamepad"),
+                            });
+                        }
                         Err(gamepad) => Self::select_vendor_profile(gamepad),
                     },
                 },
             },
-        }
+        })
     }
 
     /// Selects a profile based on the vendor string for controllers which don't have an explicit interface
@@ -127,6 +201,11 @@ impl AppleGameControllerPlatform {
             debug!(name = vendor_name, "Matching gamepad from vendor");
             match vendor_name.as_str() {
                 "Pro Controller" | "Joy-Con (L/R)" => Box::new(SwitchProfile(gamepad)),
+                "Joy-Con (L)" => Box::new(JoyConProfile(gamepad, JoyConSide::Left)),
+                "Joy-Con (R)" => Box::new(JoyConProfile(gamepad, JoyConSide::Right)),
+                // 8BitDo controllers in Switch mode report themselves as a Pro Controller. Under their
+                // own name only the Super Nintendo style ones have the Nintendo layout.
+                name if NINTENDO_LAYOUT_8BITDO.contains(&name) => Box::new(SwitchProfile(gamepad)),
                 _ => Box::new(GenericProfile(gamepad)),
             }
         } else {
@@ -135,7 +214,10 @@ impl AppleGameControllerPlatform {
         }
     }
 
-    fn register_connect_notification(&self, tx: Sender<GamepadPlatformEvent>) {
+    fn register_connect_notification(
+        &self,
+        tx: EventSender,
+    ) -> Retained<ProtocolObject<dyn NSObjectProtocol>> {
         unsafe {
             self.notification_center
                 .addObserverForName_object_queue_usingBlock(
@@ -143,82 +225,116 @@ impl AppleGameControllerPlatform {
                     None,
                     None,
                     &StackBlock::new(move |notification: NonNull<NSNotification>| {
-                        let Some(object) = notification.as_ref().object() else {
-                            if let Err(e) =
-                                tx.send(GamepadPlatformEvent::Error(GamepadError::Platform(
-                                    "Failed to get object from NSNotification".into(),
-                                )))
-                            {
-                                error!("Failed to send to controller event channel: {e}");
+                        catch_panic(&tx, "connect", || {
+                            let Some(object) = notification.as_ref().object() else {
+                                report(
+                                    &tx,
+                                    GamepadError::Platform(
+                                        "Failed to get object from NSNotification".into(),
+                                    ),
+                                );
+                                return;
+                            };
+
+                            let Some(controller) = object.downcast_ref::<GCController>() else {
+                                report(
+                                    &tx,
+                                    GamepadError::Platform(
+                                        "Failed to downcast to GCController from NSNotification object"
+                                            .into(),
+                                    ),
+                                );
+                                return;
+                            };
+
+                            // Only controllers with the extended gamepad profile are supported
+                            let Some(gamepad) = controller.extendedGamepad() else {
+                                let name = controller
+                                    .vendorName()
+                                    .map(|name| name.to_string())
+                                    .unwrap_or(String::from("Unknown Apple Gamepad"));
+                                report(&tx, GamepadError::UnsupportedController { id: None, name });
+                                return;
+                            };
+
+                            // Set the player index to -1 to mark it as an unassigned player
+                            // This is to filter the controller when finding a new player index
+                            controller.setPlayerIndex(GCControllerPlayerIndex(-1));
+
+                            // Now find the next player index by scanning existing controllers
+                            let index = Self::get_next_player_index();
+                            controller.setPlayerIndex(index);
+
+                            // The framework keeps the index unset if it has no player index left
+                            let id = index.0 as GamepadId;
+                            if controller.playerIndex().0 != index.0 {
+                                report(
+                                    &tx,
+                                    GamepadError::DeviceInit {
+                                        id,
+                                        reason: String::from("no player index available"),
+                                    },
+                                );
+                                return;
                             }
-                            return;
-                        };
-
-                        let Some(controller) = object.downcast_ref::<GCController>() else {
-                            if let Err(e) =
-                                tx.send(GamepadPlatformEvent::Error(GamepadError::Platform(
-                                    "Failed to downcast to GCController from NSNotification object"
-                                        .into(),
-                                )))
-                            {
+                            let (device_info, labels, snapshot) = match Self::init_gamepad(
+                                id,
+                                tx.clone(),
+                                gamepad,
+                            ) {
+                                Ok(init) => init,
+                                Err(error) => {
+                                    // Free the player index for the next controller
+                                    controller.setPlayerIndex(GCControllerPlayerIndex(-1));
+                                    report(&tx, error);
+                                    return;
+                                }
+                            };
+
+                            let vendor_name = controller
+                                .vendorName()
+                                .map(|name| name.to_string())
+                                .unwrap_or(String::from("Unknown Apple Gamepad"));
+
+                            info!(
+                                name = vendor_name,
+                                index = controller.playerIndex().0,
+                                "Connected"
+                            );
+
+                            if let Err(e) = tx.send(GamepadPlatformEvent::Connected {
+                                id,
+                                connection: GamepadConnection::Connected {
+                                    name: format!("{vendor_name} {id}"),
+                                    vendor_id: None,
+                                    product_id: None,
+                                },
+                                info: device_info,
+                                labels,
+                            }) {
                                 error!("Failed to send to controller event channel: {e}");
                             }
-                            return;
-                        };
-
-                        let Some(gamepad) = controller.extendedGamepad() else {
-                            if let Err(e) =
-                                tx.send(GamepadPlatformEvent::Error(GamepadError::Platform(
-                                    "Failed to get GCExtendedGamepad from GCController".into(),
-                                )))
-                            {
-                                error!("Failed to send to controller event channel: {e}");
+
+                            let timestamp = InputTimestamp::now();
+                            for change in snapshot {
+                                if let Err(e) = tx.send(GamepadPlatformEvent::InputChanged {
+                                    id,
+                                    change,
+                                    timestamp,
+                                }) {
+                                    error!("Failed to send to controller event channel: {e}");
+                                }
                             }
-                            return;
-                        };
-
-                        // Set the player index to -1 to mark it as an unassigned player
-                        // This is to filter the controller when finding a new player index
-                        controller.setPlayerIndex(GCControllerPlayerIndex(-1));
-
-                        // Now find the next player index by scanning existing controllers
-                        controller.setPlayerIndex(Self::get_next_player_index());
-
-                        Self::init_gamepad(
-                            controller.playerIndex().0 as usize,
-                            tx.clone(),
-                            gamepad,
-                        );
-
-                        let vendor_name = controller
-                            .vendorName()
-                            .map(|name| name.to_string())
-                            .unwrap_or(String::from("Unknown Apple Gamepad"));
-
-                        info!(
-                            name = vendor_name,
-                            index = controller.playerIndex().0,
-                            "Connected"
-                        );
-
-                        tx.send(GamepadPlatformEvent::Connected {
-                            id: controller.playerIndex().0 as usize,
-                            connection: GamepadConnection::Connected {
-                                name: format!(
-                                    "{vendor_name} {}",
-                                    controller.playerIndex().0 as GamepadId
-                                ),
-                                vendor_id: None,
-                                product_id: None,
-                            },
                         })
-                        .unwrap();
                     }),
-                );
+                )
         }
     }
 
-    fn register_disconnect_notification(&self, tx: Sender<GamepadPlatformEvent>) {
+    fn register_disconnect_notification(
+        &self,
+        tx: EventSender,
+    ) -> Retained<ProtocolObject<dyn NSObjectProtocol>> {
         unsafe {
             self.notification_center
                 .addObserverForName_object_queue_usingBlock(
@@ -226,30 +342,32 @@ impl AppleGameControllerPlatform {
                     None,
                     None,
                     &StackBlock::new(move |notification: NonNull<NSNotification>| {
-                        let Some(object) = notification.as_ref().object() else {
-                            return;
-                        };
-
-                        if let Some(controller) = object.downcast_ref::<GCController>() {
-                            let id = controller.playerIndex().0 as GamepadId;
-
-                            let vendor_name = controller
-                                .vendorName()
-                                .map(|name| name.to_string())
-                                .unwrap_or(String::from("Unknown Apple Gamepad"));
-
-                            info!(
-                                name = vendor_name,
-                                index = controller.playerIndex().0,
-                                "Disconnected"
-                            );
-
-                            if let Err(e) = tx.send(GamepadPlatformEvent::Disconnected { id }) {
-                                error!("Failed to send to controller event channel: {e}");
+                        catch_panic(&tx, "disconnect", || {
+                            let Some(object) = notification.as_ref().object() else {
+                                return;
+                            };
+
+                            if let Some(controller) = object.downcast_ref::<GCController>() {
+                                let id = controller.playerIndex().0 as GamepadId;
+
+                                let vendor_name = controller
+                                    .vendorName()
+                                    .map(|name| name.to_string())
+                                    .unwrap_or(String::from("Unknown Apple Gamepad"));
+
+                                info!(
+                                    name = vendor_name,
+                                    index = controller.playerIndex().0,
+                                    "Disconnected"
+                                );
+
+                                if let Err(e) = tx.send(GamepadPlatformEvent::Disconnected { id }) {
+                                    error!("Failed to send to controller event channel: {e}");
+                                }
                             }
-                        }
+                        })
                     }),
-                );
+                )
         }
     }
 }
@@ -257,12 +375,14 @@ impl AppleGameControllerPlatform {
 impl Platform for AppleGameControllerPlatform {
     type Handle = Retained<GCExtendedGamepad>;
 
-    fn new(_app: &mut App, tx: Sender<GamepadPlatformEvent>) -> Result<Self, GamepadError> {
+    fn new(_app: &mut App, tx: EventSender) -> Result<Self, GamepadError> {
         // Get the default notification center
         let notification_center = unsafe { NSNotificationCenter::defaultCenter() };
 
-        let driver = Self {
+        let mut driver = Self {
             notification_center,
+            observers: Vec::new(),
+            discovery: Arc::new(AtomicUsize::new(0)),
         };
 
         // Register gamepad connection/disconnection notifications with notification center
@@ -270,9 +390,98 @@ impl Platform for AppleGameControllerPlatform {
 
         unsafe {
             GCController::setShouldMonitorBackgroundEvents(true);
-            GCController::startWirelessControllerDiscoveryWithCompletionHandler(None);
         }
+        driver.start_discovery()?;
 
         Ok(driver)
     }
+
+    fn start_discovery(&mut self) -> Result<(), GamepadError> {
+        static SESSIONS: AtomicUsize = AtomicUsize::new(1);
+        let session = SESSIONS.fetch_add(1, Ordering::Relaxed);
+        self.discovery.store(session, Ordering::Relaxed);
+
+        let discovery = self.discovery.clone();
+        let completion = StackBlock::new(move || {
+            // Only finish the session this handler was registered for
+            let _ = discovery.compare_exchange(session, 0, Ordering::Relaxed, Ordering::Relaxed);
+        });
+
+        unsafe {
+            GCController::startWirelessControllerDiscoveryWithCompletionHandler(Some(&completion));
+        }
+
+        Ok(())
+    }
+
+    fn stop_discovery(&mut self) {
+        self.discovery.store(0, Ordering::Relaxed);
+        unsafe {
+            GCController::stopWirelessControllerDiscovery();
+        }
+    }
+
+    fn discovering(&self) -> bool {
+        self.discovery.load(Ordering::Relaxed) != 0
+    }
+
+    fn snapshot(&mut self, id: GamepadId) -> Result<Vec<GamepadChange>, GamepadError> {
+        let controllers = unsafe { GCController::controllers() };
+        let controller = controllers
+            .iter()
+            .find(|controller| unsafe { controller.playerIndex().0 } == id as isize)
+            .ok_or_else(|| GamepadError::Platform(format!("Gamepad {id} is not connected")))?;
+
+        let gamepad = unsafe { controller.extendedGamepad() }.ok_or_else(|| {
+            GamepadError::UnsupportedController {
+                id: Some(id),
+                name: String::from("Unknown Apple Gamepad"),
+            }
+        })?;
+
+        Ok(Self::get_gamepad_profile(id, gamepad.clone())?.snapshot(&gamepad))
+    }
+
+    fn shutdown(&mut self) {
+        unsafe {
+            for observer in self.observers.drain(..) {
+                self.notification_center.removeObserver(&observer);
+            }
+
+            // The value changed handlers hold a sender of the event channel
+            for controller in GCController::controllers().iter() {
+                if let Some(gamepad) = controller.extendedGamepad() {
+                    gamepad.setValueChangedHandler(std::ptr::null_mut());
+                }
+            }
+
+            GCController::setShouldMonitorBackgroundEvents(false);
+        }
+        self.stop_discovery();
+
+        debug!("Apple Game Controller platform driver shut down");
+    }
+}
+
+/// Send an error to the plugin from a driver callback
+fn report(tx: &EventSender, error: GamepadError) {
+    if let Err(e) = tx.send(GamepadPlatformEvent::Error(error)) {
+        error!("Failed to send to controller event channel: {e}");
+    }
+}
+
+/// Run the body of a driver callback, reporting a panic as an error instead of unwinding
+/// into the Objective-C runtime, which aborts the process
+fn catch_panic(tx: &EventSender, callback: &str, body: impl FnOnce()) {
+    if let Err(panic) = catch_unwind(AssertUnwindSafe(body)) {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        report(
+            tx,
+            GamepadError::Platform(format!("Panic in {callback} callback: {message}")),
+        );
+    }
 }
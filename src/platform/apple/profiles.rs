@@ -1,20 +1,22 @@
 use bevy_input::gamepad::{GamepadAxis, GamepadButton};
 use objc2::rc::Retained;
 use objc2_game_controller::{
-    GCControllerButtonInput, GCControllerDirectionPad, GCControllerElement,
-    GCDualSenseAdaptiveTrigger, GCDualSenseGamepad, GCDualShockGamepad, GCExtendedGamepad,
-    GCXboxGamepad,
+    GCControllerButtonInput, GCControllerDirectionPad, GCControllerElement, GCDualSenseGamepad,
+    GCDualShockGamepad, GCExtendedGamepad, GCXboxGamepad,
 };
 
-use crate::profile::{ButtonChange, Changed, DPadChange, Profile};
+use crate::{
+    glyph::ButtonLabels,
+    profile::{ButtonChange, DPadChange, FaceLayout, GamepadChange, JoyConSide, Profile},
+};
 
 pub(crate) trait ApplePlatformProfile: Profile {
     /// Called from the change observer callback registered on the gamepad
     /// The default implementation will call the trait impl methods `button_changed()` and `axis_changed()`
     /// which are implemented on each gamepad profile to handle mappings to Bevy [`GamepadButton`]
-    fn element_changed(&self, event: &GCControllerElement) -> Option<Changed> {
+    fn element_changed(&self, event: &GCControllerElement) -> Option<GamepadChange> {
         if let Some(button) = event.downcast_ref::<GCControllerButtonInput>() {
-            self.button_changed(button).map(Changed::Button)
+            self.button_changed(button).map(GamepadChange::Button)
         } else if let Some(axis) = event.downcast_ref::<GCControllerDirectionPad>() {
             self.axis_changed(axis)
         } else {
@@ -22,12 +24,60 @@ pub(crate) trait ApplePlatformProfile: Profile {
         }
     }
 
+    /// Map a button of the gamepad. Triggers are always mapped to the analog buttons
+    /// [`GamepadButton::LeftTrigger2`] and [`GamepadButton::RightTrigger2`], and reported as buttons,
+    /// axes or both by the plugin according to [`crate::TriggerReporting`].
     fn button_changed(&self, button: &GCControllerButtonInput) -> Option<ButtonChange>;
-    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<Changed>;
+    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<GamepadChange>;
+
+    /// Current state of every mapped button, stick and D-pad of the gamepad, to resync the plugin
+    /// when value changed callbacks were missed, such as for buttons held while connecting
+    fn snapshot(&self, gamepad: &GCExtendedGamepad) -> Vec<GamepadChange> {
+        unsafe {
+            let buttons = gamepad.allButtons();
+            let dpads = gamepad.allDpads();
+            buttons
+                .iter()
+                .filter_map(|button| self.button_changed(&button).map(GamepadChange::Button))
+                .chain(dpads.iter().filter_map(|dpad| self.axis_changed(&dpad)))
+                .collect()
+        }
+    }
+}
+
+/// Map the triggers of an extended gamepad, shared by the profiles so every controller reports its
+/// triggers as the analog buttons [`GamepadButton::LeftTrigger2`] and [`GamepadButton::RightTrigger2`]
+fn trigger_changed(
+    gamepad: &GCExtendedGamepad,
+    button: &GCControllerButtonInput,
+) -> Option<ButtonChange> {
+    unsafe {
+        if button == &*gamepad.leftTrigger() {
+            return Some(ButtonChange::new(
+                GamepadButton::LeftTrigger2,
+                button.value(),
+            ));
+        }
+        if button == &*gamepad.rightTrigger() {
+            return Some(ButtonChange::new(
+                GamepadButton::RightTrigger2,
+                button.value(),
+            ));
+        }
+    }
+    None
 }
 
 pub struct DualSenseProfile(pub Retained<GCDualSenseGamepad>);
-impl Profile for DualSenseProfile {}
+impl Profile for DualSenseProfile {
+    fn name(&self) -> &'static str {
+        "DualSense"
+    }
+
+    fn button_labels(&self) -> ButtonLabels {
+        ButtonLabels::PLAYSTATION
+    }
+}
 
 impl ApplePlatformProfile for DualSenseProfile {
     fn button_changed(&self, button: &GCControllerButtonInput) -> Option<ButtonChange> {
@@ -80,29 +130,18 @@ impl ApplePlatformProfile for DualSenseProfile {
                 return Some(ButtonChange::new(GamepadButton::C, button.value()));
             }
 
-            if let Some(trigger) = button.downcast_ref::<GCDualSenseAdaptiveTrigger>() {
-                if trigger == &*self.0.rightTrigger() {
-                    return Some(ButtonChange::new(
-                        GamepadButton::RightTrigger2,
-                        button.value(),
-                    ));
-                }
-                if trigger == &*self.0.leftTrigger() {
-                    return Some(ButtonChange::new(
-                        GamepadButton::LeftTrigger2,
-                        button.value(),
-                    ));
-                }
+            if let Some(change) = trigger_changed(&self.0, button) {
+                return Some(change);
             }
         }
 
         None
     }
 
-    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<Changed> {
+    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<GamepadChange> {
         unsafe {
             if axis == &*self.0.leftThumbstick() {
-                return Some(Changed::DualAxis {
+                return Some(GamepadChange::DualAxis {
                     x_axis: GamepadAxis::LeftStickX,
                     x_value: axis.xAxis().value(),
                     y_axis: GamepadAxis::LeftStickY,
@@ -110,7 +149,7 @@ impl ApplePlatformProfile for DualSenseProfile {
                 });
             }
             if axis == &*self.0.rightThumbstick() {
-                return Some(Changed::DualAxis {
+                return Some(GamepadChange::DualAxis {
                     x_axis: GamepadAxis::RightStickX,
                     x_value: axis.xAxis().value(),
                     y_axis: GamepadAxis::RightStickY,
@@ -119,7 +158,7 @@ impl ApplePlatformProfile for DualSenseProfile {
             }
 
             if axis == &*self.0.dpad() {
-                return Some(Changed::DPad(DPadChange::new(
+                return Some(GamepadChange::DPad(DPadChange::new(
                     axis.up().value(),
                     axis.down().value(),
                     axis.left().value(),
@@ -127,29 +166,21 @@ impl ApplePlatformProfile for DualSenseProfile {
                 )));
             }
 
-            if let Some(trigger) = axis.downcast_ref::<GCDualSenseAdaptiveTrigger>() {
-                if trigger == &*self.0.rightTrigger() {
-                    return Some(Changed::SingleAxis {
-                        axis: GamepadAxis::RightZ,
-                        value: trigger.value(),
-                    });
-                }
-
-                if trigger == &*self.0.leftTrigger() {
-                    return Some(Changed::SingleAxis {
-                        axis: GamepadAxis::LeftZ,
-                        value: trigger.value(),
-                    });
-                }
-            }
-
             None
         }
     }
 }
 
 pub struct DualShockProfile(pub Retained<GCDualShockGamepad>);
-impl Profile for DualShockProfile {}
+impl Profile for DualShockProfile {
+    fn name(&self) -> &'static str {
+        "DualShock"
+    }
+
+    fn button_labels(&self) -> ButtonLabels {
+        ButtonLabels::PLAYSTATION
+    }
+}
 
 impl ApplePlatformProfile for DualShockProfile {
     fn button_changed(&self, button: &GCControllerButtonInput) -> Option<ButtonChange> {
@@ -203,26 +234,17 @@ impl ApplePlatformProfile for DualShockProfile {
                 }
             }
 
-            if button == &*self.0.rightTrigger() {
-                return Some(ButtonChange::new(
-                    GamepadButton::RightTrigger2,
-                    button.value(),
-                ));
-            }
-            if button == &*self.0.leftTrigger() {
-                return Some(ButtonChange::new(
-                    GamepadButton::LeftTrigger2,
-                    button.value(),
-                ));
+            if let Some(change) = trigger_changed(&self.0, button) {
+                return Some(change);
             }
         }
         None
     }
 
-    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<Changed> {
+    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<GamepadChange> {
         unsafe {
             if axis == &*self.0.leftThumbstick() {
-                return Some(Changed::DualAxis {
+                return Some(GamepadChange::DualAxis {
                     x_axis: GamepadAxis::LeftStickX,
                     x_value: axis.xAxis().value(),
                     y_axis: GamepadAxis::LeftStickY,
@@ -230,7 +252,7 @@ impl ApplePlatformProfile for DualShockProfile {
                 });
             }
             if axis == &*self.0.rightThumbstick() {
-                return Some(Changed::DualAxis {
+                return Some(GamepadChange::DualAxis {
                     x_axis: GamepadAxis::RightStickX,
                     x_value: axis.xAxis().value(),
                     y_axis: GamepadAxis::RightStickY,
@@ -239,7 +261,7 @@ impl ApplePlatformProfile for DualShockProfile {
             }
 
             if axis == &*self.0.dpad() {
-                return Some(Changed::DPad(DPadChange::new(
+                return Some(GamepadChange::DPad(DPadChange::new(
                     axis.up().value(),
                     axis.down().value(),
                     axis.left().value(),
@@ -253,7 +275,15 @@ impl ApplePlatformProfile for DualShockProfile {
 }
 
 pub struct XboxProfile(pub Retained<GCXboxGamepad>);
-impl Profile for XboxProfile {}
+impl Profile for XboxProfile {
+    fn name(&self) -> &'static str {
+        "Xbox"
+    }
+
+    fn button_labels(&self) -> ButtonLabels {
+        ButtonLabels::XBOX
+    }
+}
 
 impl ApplePlatformProfile for XboxProfile {
     fn button_changed(&self, button: &GCControllerButtonInput) -> Option<ButtonChange> {
@@ -301,26 +331,17 @@ impl ApplePlatformProfile for XboxProfile {
                 ));
             }
 
-            if button == &*self.0.rightTrigger() {
-                return Some(ButtonChange::new(
-                    GamepadButton::RightTrigger2,
-                    button.value(),
-                ));
-            }
-            if button == &*self.0.leftTrigger() {
-                return Some(ButtonChange::new(
-                    GamepadButton::LeftTrigger2,
-                    button.value(),
-                ));
+            if let Some(change) = trigger_changed(&self.0, button) {
+                return Some(change);
             }
         }
         None
     }
 
-    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<Changed> {
+    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<GamepadChange> {
         unsafe {
             if axis == &*self.0.leftThumbstick() {
-                return Some(Changed::DualAxis {
+                return Some(GamepadChange::DualAxis {
                     x_axis: GamepadAxis::LeftStickX,
                     x_value: axis.xAxis().value(),
                     y_axis: GamepadAxis::LeftStickY,
@@ -328,7 +349,7 @@ impl ApplePlatformProfile for XboxProfile {
                 });
             }
             if axis == &*self.0.rightThumbstick() {
-                return Some(Changed::DualAxis {
+                return Some(GamepadChange::DualAxis {
                     x_axis: GamepadAxis::RightStickX,
                     x_value: axis.xAxis().value(),
                     y_axis: GamepadAxis::RightStickY,
@@ -337,7 +358,7 @@ impl ApplePlatformProfile for XboxProfile {
             }
 
             if axis == &*self.0.dpad() {
-                return Some(Changed::DPad(DPadChange::new(
+                return Some(GamepadChange::DPad(DPadChange::new(
                     axis.up().value(),
                     axis.down().value(),
                     axis.left().value(),
@@ -349,8 +370,23 @@ impl ApplePlatformProfile for XboxProfile {
     }
 }
 
+/// Nintendo Switch Pro Controller, paired Joy-Cons and Switch compatible controllers such as the
+/// 8BitDo SN30 Pro.
+/// Face buttons are mapped positionally, and remapped by the plugin according to the active [`crate::FaceButtonConvention`]
 pub struct SwitchProfile(pub Retained<GCExtendedGamepad>);
-impl Profile for SwitchProfile {}
+impl Profile for SwitchProfile {
+    fn name(&self) -> &'static str {
+        "Switch"
+    }
+
+    fn button_labels(&self) -> ButtonLabels {
+        ButtonLabels::NINTENDO
+    }
+
+    fn face_layout(&self) -> FaceLayout {
+        FaceLayout::Nintendo
+    }
+}
 
 impl ApplePlatformProfile for SwitchProfile {
     fn button_changed(&self, button: &GCControllerButtonInput) -> Option<ButtonChange> {
@@ -398,17 +434,8 @@ impl ApplePlatformProfile for SwitchProfile {
                 ));
             }
 
-            if button == &*self.0.rightTrigger() {
-                return Some(ButtonChange::new(
-                    GamepadButton::RightTrigger2,
-                    button.value(),
-                ));
-            }
-            if button == &*self.0.leftTrigger() {
-                return Some(ButtonChange::new(
-                    GamepadButton::LeftTrigger2,
-                    button.value(),
-                ));
+            if let Some(change) = trigger_changed(&self.0, button) {
+                return Some(change);
             }
 
             if let Some(name) = button.localizedName() {
@@ -420,10 +447,10 @@ impl ApplePlatformProfile for SwitchProfile {
         None
     }
 
-    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<Changed> {
+    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<GamepadChange> {
         unsafe {
             if axis == &*self.0.leftThumbstick() {
-                return Some(Changed::DualAxis {
+                return Some(GamepadChange::DualAxis {
                     x_axis: GamepadAxis::LeftStickX,
                     x_value: axis.xAxis().value(),
                     y_axis: GamepadAxis::LeftStickY,
@@ -431,7 +458,7 @@ impl ApplePlatformProfile for SwitchProfile {
                 });
             }
             if axis == &*self.0.rightThumbstick() {
-                return Some(Changed::DualAxis {
+                return Some(GamepadChange::DualAxis {
                     x_axis: GamepadAxis::RightStickX,
                     x_value: axis.xAxis().value(),
                     y_axis: GamepadAxis::RightStickY,
@@ -439,7 +466,127 @@ impl ApplePlatformProfile for SwitchProfile {
                 });
             }
             if axis == &*self.0.dpad() {
-                return Some(Changed::DPad(DPadChange::new(
+                return Some(GamepadChange::DPad(DPadChange::new(
+                    axis.up().value(),
+                    axis.down().value(),
+                    axis.left().value(),
+                    axis.right().value(),
+                )));
+            }
+            None
+        }
+    }
+}
+
+/// A single Joy-Con. Elements are mapped upright as one half of a Joy-Con pair, the plugin rotates
+/// the mapping when the Joy-Con is held sideways on its own.
+pub struct JoyConProfile(pub Retained<GCExtendedGamepad>, pub JoyConSide);
+impl Profile for JoyConProfile {
+    fn name(&self) -> &'static str {
+        match self.1 {
+            JoyConSide::Left => "Joy-Con (L)",
+            JoyConSide::Right => "Joy-Con (R)",
+        }
+    }
+
+    fn button_labels(&self) -> ButtonLabels {
+        ButtonLabels::NINTENDO
+    }
+
+    fn face_layout(&self) -> FaceLayout {
+        FaceLayout::Nintendo
+    }
+
+    fn joy_con(&self) -> Option<JoyConSide> {
+        Some(self.1)
+    }
+}
+
+impl ApplePlatformProfile for JoyConProfile {
+    fn button_changed(&self, button: &GCControllerButtonInput) -> Option<ButtonChange> {
+        // A single Joy-Con only has one shoulder, trigger and stick, which are mapped to its side
+        let (shoulder, trigger, thumb) = match self.1 {
+            JoyConSide::Left => (
+                GamepadButton::LeftTrigger,
+                GamepadButton::LeftTrigger2,
+                GamepadButton::LeftThumb,
+            ),
+            JoyConSide::Right => (
+                GamepadButton::RightTrigger,
+                GamepadButton::RightTrigger2,
+                GamepadButton::RightThumb,
+            ),
+        };
+
+        unsafe {
+            if button == &*self.0.buttonA() {
+                return Some(ButtonChange::new(GamepadButton::East, button.value()));
+            }
+            if button == &*self.0.buttonB() {
+                return Some(ButtonChange::new(GamepadButton::South, button.value()));
+            }
+            if button == &*self.0.buttonX() {
+                return Some(ButtonChange::new(GamepadButton::North, button.value()));
+            }
+            if button == &*self.0.buttonY() {
+                return Some(ButtonChange::new(GamepadButton::West, button.value()));
+            }
+            if button == &*self.0.buttonMenu() {
+                return Some(ButtonChange::new(GamepadButton::Start, button.value()));
+            }
+            if let Some(options) = self.0.buttonOptions() {
+                if button == &*options {
+                    return Some(ButtonChange::new(GamepadButton::Select, button.value()));
+                }
+            }
+            if let Some(home) = self.0.buttonHome() {
+                if button == &*home {
+                    return Some(ButtonChange::new(GamepadButton::Mode, button.value()));
+                }
+            }
+            if let Some(left_thumb) = self.0.leftThumbstickButton() {
+                if button == &*left_thumb {
+                    return Some(ButtonChange::new(thumb, button.value()));
+                }
+            }
+            if let Some(right_thumb) = self.0.rightThumbstickButton() {
+                if button == &*right_thumb {
+                    return Some(ButtonChange::new(thumb, button.value()));
+                }
+            }
+            if button == &*self.0.leftShoulder() || button == &*self.0.rightShoulder() {
+                return Some(ButtonChange::new(shoulder, button.value()));
+            }
+            if button == &*self.0.leftTrigger() || button == &*self.0.rightTrigger() {
+                return Some(ButtonChange::new(trigger, button.value()));
+            }
+
+            if let Some(name) = button.localizedName() {
+                if name.to_string() == "Share Button" {
+                    return Some(ButtonChange::new(GamepadButton::C, button.value()));
+                }
+            }
+        }
+        None
+    }
+
+    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<GamepadChange> {
+        let (x_axis, y_axis) = match self.1 {
+            JoyConSide::Left => (GamepadAxis::LeftStickX, GamepadAxis::LeftStickY),
+            JoyConSide::Right => (GamepadAxis::RightStickX, GamepadAxis::RightStickY),
+        };
+
+        unsafe {
+            if axis == &*self.0.leftThumbstick() || axis == &*self.0.rightThumbstick() {
+                return Some(GamepadChange::DualAxis {
+                    x_axis,
+                    x_value: axis.xAxis().value(),
+                    y_axis,
+                    y_value: axis.yAxis().value(),
+                });
+            }
+            if axis == &*self.0.dpad() {
+                return Some(GamepadChange::DPad(DPadChange::new(
                     axis.up().value(),
                     axis.down().value(),
                     axis.left().value(),
@@ -452,7 +599,15 @@ impl ApplePlatformProfile for SwitchProfile {
 }
 
 pub struct GenericProfile(pub Retained<GCExtendedGamepad>);
-impl Profile for GenericProfile {}
+impl Profile for GenericProfile {
+    fn name(&self) -> &'static str {
+        "Generic"
+    }
+
+    fn button_labels(&self) -> ButtonLabels {
+        ButtonLabels::GENERIC
+    }
+}
 
 impl ApplePlatformProfile for GenericProfile {
     fn button_changed(&self, button: &GCControllerButtonInput) -> Option<ButtonChange> {
@@ -500,17 +655,8 @@ impl ApplePlatformProfile for GenericProfile {
                 ));
             }
 
-            if button == &*self.0.rightTrigger() {
-                return Some(ButtonChange::new(
-                    GamepadButton::RightTrigger2,
-                    button.value(),
-                ));
-            }
-            if button == &*self.0.leftTrigger() {
-                return Some(ButtonChange::new(
-                    GamepadButton::LeftTrigger2,
-                    button.value(),
-                ));
+            if let Some(change) = trigger_changed(&self.0, button) {
+                return Some(change);
             }
 
             if let Some(name) = button.localizedName() {
@@ -522,10 +668,10 @@ impl ApplePlatformProfile for GenericProfile {
         None
     }
 
-    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<Changed> {
+    fn axis_changed(&self, axis: &GCControllerDirectionPad) -> Option<GamepadChange> {
         unsafe {
             if axis == &*self.0.leftThumbstick() {
-                return Some(Changed::DualAxis {
+                return Some(GamepadChange::DualAxis {
                     x_axis: GamepadAxis::LeftStickX,
                     x_value: axis.xAxis().value(),
                     y_axis: GamepadAxis::LeftStickY,
@@ -533,7 +679,7 @@ impl ApplePlatformProfile for GenericProfile {
                 });
             }
             if axis == &*self.0.rightThumbstick() {
-                return Some(Changed::DualAxis {
+                return Some(GamepadChange::DualAxis {
                     x_axis: GamepadAxis::RightStickX,
                     x_value: axis.xAxis().value(),
                     y_axis: GamepadAxis::RightStickY,
@@ -541,7 +687,7 @@ impl ApplePlatformProfile for GenericProfile {
                 });
             }
             if axis == &*self.0.dpad() {
-                return Some(Changed::DPad(DPadChange::new(
+                return Some(GamepadChange::DPad(DPadChange::new(
                     axis.up().value(),
                     axis.down().value(),
                     axis.left().value(),
@@ -552,3 +698,119 @@ impl ApplePlatformProfile for GenericProfile {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_input::gamepad::GamepadInput;
+    use objc2_game_controller::GCController;
+
+    use super::*;
+    use crate::TriggerReporting;
+
+    /// Profiles which can be built on the extended gamepad of a snapshot controller. The DualSense,
+    /// DualShock and Xbox profiles need a device of their kind, and map triggers with the same
+    /// [`trigger_changed`] as the Switch and Generic profiles.
+    fn profiles(gamepad: &Retained<GCExtendedGamepad>) -> Vec<Box<dyn ApplePlatformProfile>> {
+        vec![
+            Box::new(SwitchProfile(gamepad.clone())),
+            Box::new(JoyConProfile(gamepad.clone(), JoyConSide::Left)),
+            Box::new(JoyConProfile(gamepad.clone(), JoyConSide::Right)),
+            Box::new(GenericProfile(gamepad.clone())),
+        ]
+    }
+
+    fn gamepad() -> Retained<GCExtendedGamepad> {
+        unsafe {
+            let controller = GCController::controllerWithExtendedGamepad();
+            let gamepad = controller.extendedGamepad().unwrap();
+            gamepad.leftTrigger().setValue(0.5);
+            gamepad.rightTrigger().setValue(0.25);
+            gamepad
+        }
+    }
+
+    fn is_trigger(button: GamepadButton) -> bool {
+        matches!(
+            button,
+            GamepadButton::LeftTrigger2 | GamepadButton::RightTrigger2
+        )
+    }
+
+    #[test]
+    fn triggers_mapped_to_analog_buttons() {
+        let gamepad = gamepad();
+        let (left, right) = unsafe { (gamepad.leftTrigger(), gamepad.rightTrigger()) };
+
+        for profile in profiles(&gamepad) {
+            let name = profile.name();
+            let left_change = profile.button_changed(&left).unwrap();
+            let right_change = profile.button_changed(&right).unwrap();
+            assert_eq!(left_change.value(), 0.5, "{name}");
+            assert_eq!(right_change.value(), 0.25, "{name}");
+
+            // A single Joy-Con maps its one trigger to its side
+            let expected = match profile.joy_con() {
+                Some(JoyConSide::Left) => [GamepadButton::LeftTrigger2; 2],
+                Some(JoyConSide::Right) => [GamepadButton::RightTrigger2; 2],
+                None => [GamepadButton::LeftTrigger2, GamepadButton::RightTrigger2],
+            };
+            assert_eq!(
+                [left_change.button(), right_change.button()],
+                expected,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn only_triggers_mapped_to_analog_buttons() {
+        let gamepad = gamepad();
+        let (left, right) = unsafe { (gamepad.leftTrigger(), gamepad.rightTrigger()) };
+        let buttons = unsafe { gamepad.allButtons() };
+
+        for profile in profiles(&gamepad) {
+            for button in buttons.iter() {
+                let trigger = *button == *left || *button == *right;
+                let mapped = profile
+                    .button_changed(&button)
+                    .map(|change| change.button());
+                assert_eq!(
+                    mapped.is_some_and(is_trigger),
+                    trigger,
+                    "{} maps {mapped:?}",
+                    profile.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn trigger_reporting_applied_to_profiles() {
+        let gamepad = gamepad();
+        let right = unsafe { gamepad.rightTrigger() };
+
+        for profile in profiles(&gamepad) {
+            let change = profile.button_changed(&right).unwrap();
+            let button = change.button();
+            let axis = match button {
+                GamepadButton::LeftTrigger2 => GamepadAxis::LeftZ,
+                _ => GamepadAxis::RightZ,
+            };
+
+            for (triggers, expected) in [
+                (TriggerReporting::Button, vec![GamepadInput::from(button)]),
+                (TriggerReporting::Axis, vec![GamepadInput::from(axis)]),
+                (
+                    TriggerReporting::Both,
+                    vec![GamepadInput::from(button), GamepadInput::from(axis)],
+                ),
+            ] {
+                let reported: Vec<_> = triggers
+                    .apply(button.into(), change.value())
+                    .map(|(input, _)| input)
+                    .collect();
+                assert_eq!(reported, expected, "{} {triggers:?}", profile.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy_app::{App, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time};
+
+use super::{GamepadPlatformEvent, Platform};
+use crate::{
+    channel::EventSender, error::GamepadError, replay::GamepadReplay, timestamp::InputTimestamp,
+};
+
+/// Platform driver which plays back a [`GamepadReplay`] recording instead of reading devices.
+/// Available on every target, so recorded input can be reproduced where no controllers exist.
+pub struct ReplayPlatform;
+
+/// Pending events of the recording being played back
+#[derive(Resource)]
+struct ReplayState {
+    events: VecDeque<(Duration, GamepadPlatformEvent)>,
+    tx: EventSender,
+}
+
+/// Release every recorded event whose timestamp has been reached by the elapsed time
+fn replay_system(time: Res<Time>, real_time: Res<Time<Real>>, mut state: ResMut<ReplayState>) {
+    let start = real_time
+        .first_update()
+        .map_or_else(InputTimestamp::now, InputTimestamp::from_instant);
+
+    while state
+        .events
+        .front()
+        .is_some_and(|(timestamp, _)| *timestamp <= time.elapsed())
+    {
+        let Some((time, mut event)) = state.events.pop_front() else {
+            break;
+        };
+
+        // Inputs are stamped as captured at their recorded offset from the first frame, which keeps
+        // their position relative to the frame and fixed timestep clocks deterministic
+        if let GamepadPlatformEvent::InputChanged { timestamp, .. } = &mut event {
+            *timestamp = InputTimestamp::from_captured(start.captured() + time);
+        }
+
+        if let Err(e) = state.tx.send(event) {
+            bevy_log::error!("Failed to send to controller event channel: {e}");
+        }
+    }
+}
+
+impl Platform for ReplayPlatform {
+    type Handle = ();
+
+    fn new(app: &mut App, tx: EventSender) -> Result<Self, GamepadError> {
+        // Events are released by the elapsed time
+        if !app.world().contains_resource::<Time>()
+            || !app.world().contains_resource::<Time<Real>>()
+        {
+            return Err(GamepadError::Platform(
+                "Replaying a recording needs the TimePlugin, added before the GamepadPlugin".into(),
+            ));
+        }
+
+        let replay = app
+            .world_mut()
+            .remove_resource::<GamepadReplay>()
+            .ok_or_else(|| GamepadError::Platform("No GamepadReplay resource to play".into()))?;
+
+        bevy_log::info!(events = replay.len(), "Replaying gamepad recording");
+
+        app.insert_resource(ReplayState {
+            events: replay.events.into(),
+            tx,
+        });
+        app.add_systems(
+            PreUpdate,
+            replay_system
+                .in_set(crate::GamepadPluginSystems::Platform)
+                .before(crate::platform_system),
+        );
+
+        Ok(Self)
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use bevy_app::App;
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::{GamepadConnection, GamepadInput};
+use bevy_utils::HashMap;
+
+use super::{GamepadPlatformEvent, Platform};
+use crate::{
+    GamepadDeviceInfo, GamepadId,
+    channel::EventSender,
+    error::GamepadError,
+    glyph::ButtonLabels,
+    profile::{ButtonChange, GamepadChange},
+    timestamp::InputTimestamp,
+};
+
+/// Gamepads driven from code instead of devices, for tests and automation.
+///
+/// Insert this resource before adding the plugin to use it as the platform driver. Clones share the
+/// channel, so a clone kept outside the app can still send once the plugin is built. The channel is
+/// released when the app exits or is dropped, after which the gamepads can drive another app.
+///
+/// The state of every input sent is kept, and reported to the plugin when it resyncs a gamepad.
+///
+/// ```ignore
+/// let gamepads = VirtualGamepads::default();
+/// app.insert_resource(gamepads.clone());
+/// app.add_plugins(GamepadPlugin);
+///
+/// gamepads.connect(0, "Virtual Xbox", GamepadDeviceInfo::new("Xbox", FaceLayout::Standard), ButtonLabels::XBOX)?;
+/// gamepads.input(0, GamepadChange::Button(ButtonChange::new(GamepadButton::South, 1.0)))?;
+/// app.update();
+/// ```
+#[derive(Resource, Debug, Default, Clone)]
+pub struct VirtualGamepads {
+    tx: Arc<Mutex<Option<EventSender>>>,
+    inputs: Arc<Mutex<HashMap<GamepadId, HashMap<GamepadInput, f32>>>>,
+}
+
+impl VirtualGamepads {
+    /// Connect a virtual gamepad with the given platform id
+    pub fn connect(
+        &self,
+        id: GamepadId,
+        name: impl Into<String>,
+        info: GamepadDeviceInfo,
+        labels: ButtonLabels,
+    ) -> Result<(), GamepadError> {
+        self.send(GamepadPlatformEvent::Connected {
+            id,
+            connection: GamepadConnection::Connected {
+                name: name.into(),
+                vendor_id: None,
+                product_id: None,
+            },
+            info,
+            labels,
+        })
+    }
+
+    /// Disconnect the virtual gamepad with the given platform id
+    pub fn disconnect(&self, id: GamepadId) -> Result<(), GamepadError> {
+        self.send(GamepadPlatformEvent::Disconnected { id })
+    }
+
+    /// Change an input of a virtual gamepad, captured now
+    pub fn input(&self, id: GamepadId, change: GamepadChange) -> Result<(), GamepadError> {
+        self.send(GamepadPlatformEvent::InputChanged {
+            id,
+            change,
+            timestamp: InputTimestamp::now(),
+        })
+    }
+
+    /// Report an error as the platform driver would
+    pub fn error(&self, error: GamepadError) -> Result<(), GamepadError> {
+        self.send(GamepadPlatformEvent::Error(error))
+    }
+
+    /// Report a change of an element the profile of the device has no mapping for, as the platform
+    /// driver would
+    pub fn unhandled(&self) -> Result<(), GamepadError> {
+        let tx = self.lock();
+        let tx = tx.as_ref().ok_or(GamepadError::ChannelClosed)?;
+        tx.unhandled();
+        Ok(())
+    }
+
+    /// Send any platform event. Fails with [`GamepadError::ChannelClosed`] before the plugin is
+    /// built and after the app has exited or been dropped.
+    pub fn send(&self, event: GamepadPlatformEvent) -> Result<(), GamepadError> {
+        let tx = self.lock();
+        let tx = tx.as_ref().ok_or(GamepadError::ChannelClosed)?;
+
+        let mut inputs = self.inputs.lock().unwrap_or_else(PoisonError::into_inner);
+        match &event {
+            GamepadPlatformEvent::InputChanged { id, change, .. } => {
+                inputs
+                    .entry(*id)
+                    .or_default()
+                    .extend(change.clone().into_inputs());
+            }
+            GamepadPlatformEvent::Disconnected { id } => {
+                inputs.remove(id);
+            }
+            _ => {}
+        }
+
+        tx.send(event)
+    }
+
+    /// Current state of every input sent for a gamepad, as changes from a released state
+    fn snapshot(&self, id: GamepadId) -> Vec<GamepadChange> {
+        let inputs = self.inputs.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(inputs) = inputs.get(&id) else {
+            return Vec::new();
+        };
+
+        inputs
+            .iter()
+            .map(|(input, value)| match *input {
+                GamepadInput::Button(button) => {
+                    GamepadChange::Button(ButtonChange::new(button, *value))
+                }
+                GamepadInput::Axis(axis) => GamepadChange::SingleAxis {
+                    axis,
+                    value: *value,
+                },
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<EventSender>> {
+        self.tx.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Platform driver fed by a [`VirtualGamepads`] resource. Available on every target.
+pub struct VirtualPlatform {
+    gamepads: VirtualGamepads,
+    discovering: bool,
+}
+
+impl Platform for VirtualPlatform {
+    type Handle = ();
+
+    fn new(app: &mut App, tx: EventSender) -> Result<Self, GamepadError> {
+        let gamepads = app
+            .world()
+            .get_resource::<VirtualGamepads>()
+            .ok_or_else(|| GamepadError::Platform("No VirtualGamepads resource to drive".into()))?
+            .clone();
+
+        {
+            let mut current = gamepads.lock();
+            if current.is_some() {
+                return Err(GamepadError::Platform(
+                    "VirtualGamepads is already driven by another app".into(),
+                ));
+            }
+            *current = Some(tx);
+        }
+
+        bevy_log::info!("Using virtual gamepads");
+
+        Ok(Self {
+            gamepads,
+            discovering: false,
+        })
+    }
+
+    fn start_discovery(&mut self) -> Result<(), GamepadError> {
+        self.discovering = true;
+        Ok(())
+    }
+
+    fn stop_discovery(&mut self) {
+        self.discovering = false;
+    }
+
+    fn discovering(&self) -> bool {
+        self.discovering
+    }
+
+    fn snapshot(&mut self, id: GamepadId) -> Result<Vec<GamepadChange>, GamepadError> {
+        Ok(self.gamepads.snapshot(id))
+    }
+
+    fn shutdown(&mut self) {
+        self.gamepads.lock().take();
+    }
+}
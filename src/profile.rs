@@ -1,9 +1,131 @@
-use bevy_input::gamepad::{GamepadAxis, GamepadButton};
+use bevy_ecs::component::Component;
+use bevy_input::gamepad::{GamepadAxis, GamepadButton, GamepadInput};
 
-pub trait Profile {}
+use crate::glyph::ButtonLabels;
+
+#[cfg_attr(not(target_vendor = "apple"), allow(dead_code))]
+pub trait Profile {
+    /// Name of the profile, reported in [`crate::GamepadDeviceInfo`]
+    fn name(&self) -> &'static str;
+
+    /// Display labels for the buttons of the controller this profile maps
+    fn button_labels(&self) -> ButtonLabels;
+
+    /// Physical arrangement of the face buttons on the controller
+    fn face_layout(&self) -> FaceLayout {
+        FaceLayout::Standard
+    }
+
+    /// Which half of a Joy-Con pair the controller is, for profiles mapping a single Joy-Con
+    fn joy_con(&self) -> Option<JoyConSide> {
+        None
+    }
+}
+
+/// Side of a single Nintendo Joy-Con
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum JoyConSide {
+    Left,
+    Right,
+}
+
+/// Physical arrangement of the labelled face buttons on a controller
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum FaceLayout {
+    /// A on the bottom, B on the right (Xbox, PlayStation and Apple extended gamepads)
+    #[default]
+    Standard,
+
+    /// A on the right, B on the bottom (Nintendo Switch and compatibles)
+    Nintendo,
+}
+
+/// How the face buttons of a [`FaceLayout::Nintendo`] controller are mapped to [`GamepadButton`].
+/// Controllers with a [`FaceLayout::Standard`] layout map identically under both conventions.
+///
+/// The global default is set in [`crate::GamepadPluginSettings`], and can be overridden
+/// per gamepad by inserting this component on the gamepad entity.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum FaceButtonConvention {
+    /// Buttons are mapped by position. The bottom button (B on a Switch controller) is always [`GamepadButton::South`]
+    #[default]
+    Positional,
+
+    /// Buttons are mapped by label. The button labelled A is always [`GamepadButton::South`] (confirm),
+    /// B is [`GamepadButton::East`], X is [`GamepadButton::West`] and Y is [`GamepadButton::North`]
+    Labelled,
+}
+
+impl FaceButtonConvention {
+    /// Remap a positionally mapped face button of a controller with the given layout to this convention
+    pub fn apply(self, layout: FaceLayout, button: GamepadButton) -> GamepadButton {
+        match (self, layout) {
+            (FaceButtonConvention::Labelled, FaceLayout::Nintendo) => match button {
+                GamepadButton::South => GamepadButton::East,
+                GamepadButton::East => GamepadButton::South,
+                GamepadButton::North => GamepadButton::West,
+                GamepadButton::West => GamepadButton::North,
+                other => other,
+            },
+            _ => button,
+        }
+    }
+}
+
+/// How triggers are reported to the game, set in [`crate::GamepadPluginSettings`].
+///
+/// Every profile reports the analog value of its triggers as [`GamepadButton::LeftTrigger2`] and
+/// [`GamepadButton::RightTrigger2`]. The policy is applied after the profile mapping, so every
+/// controller and platform reports triggers the same way.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum TriggerReporting {
+    /// Analog buttons [`GamepadButton::LeftTrigger2`] and [`GamepadButton::RightTrigger2`]
+    #[default]
+    Button,
+
+    /// Axes [`GamepadAxis::LeftZ`] and [`GamepadAxis::RightZ`], from `0.0` released to `1.0` fully pulled
+    Axis,
+
+    /// Both the buttons and the axes, with the same value
+    Both,
+}
+
+impl TriggerReporting {
+    /// Report a trigger input, either the button or the axis of a trigger, according to this policy.
+    /// Any other input is reported as it is.
+    pub fn apply(
+        self,
+        input: GamepadInput,
+        value: f32,
+    ) -> impl Iterator<Item = (GamepadInput, f32)> {
+        let (button, axis) = match input {
+            GamepadInput::Button(GamepadButton::LeftTrigger2)
+            | GamepadInput::Axis(GamepadAxis::LeftZ) => {
+                (GamepadButton::LeftTrigger2, GamepadAxis::LeftZ)
+            }
+            GamepadInput::Button(GamepadButton::RightTrigger2)
+            | GamepadInput::Axis(GamepadAxis::RightZ) => {
+                (GamepadButton::RightTrigger2, GamepadAxis::RightZ)
+            }
+            input => return [Some((input, value)), None].into_iter().flatten(),
+        };
+
+        let reported = match self {
+            TriggerReporting::Button => [Some((button.into(), value)), None],
+            TriggerReporting::Axis => [Some((axis.into(), value)), None],
+            TriggerReporting::Both => [Some((button.into(), value)), Some((axis.into(), value))],
+        };
+        reported.into_iter().flatten()
+    }
+}
 
 /// A button change result returned from [`Profile`] mapped to bevy [`GamepadButton`]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct ButtonChange {
     button: GamepadButton,
     value: f32,
@@ -23,7 +145,8 @@ impl ButtonChange {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct DPadChange {
     up: f32,
     down: f32,
@@ -55,8 +178,9 @@ impl DPadChange {
     }
 }
 
-#[derive(Debug)]
-pub enum Changed {
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadChange {
     Button(ButtonChange),
     DualAxis {
         x_axis: GamepadAxis,
@@ -69,4 +193,88 @@ pub enum Changed {
         value: f32,
     },
     DPad(DPadChange),
+    Buttons(Vec<ButtonChange>),
+}
+
+impl GamepadChange {
+    /// Flatten the change into the value of each button and axis it sets
+    pub fn into_inputs(self) -> Vec<(GamepadInput, f32)> {
+        match self {
+            GamepadChange::Button(change) => vec![(change.button().into(), change.value())],
+            GamepadChange::Buttons(changes) => changes
+                .into_iter()
+                .map(|change| (change.button().into(), change.value()))
+                .collect(),
+            GamepadChange::DualAxis {
+                x_axis,
+                x_value,
+                y_axis,
+                y_value,
+            } => vec![(x_axis.into(), x_value), (y_axis.into(), y_value)],
+            GamepadChange::SingleAxis { axis, value } => vec![(axis.into(), value)],
+            GamepadChange::DPad(dpad) => vec![
+                (GamepadButton::DPadUp.into(), dpad.up()),
+                (GamepadButton::DPadDown.into(), dpad.down()),
+                (GamepadButton::DPadLeft.into(), dpad.left()),
+                (GamepadButton::DPadRight.into(), dpad.right()),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FACE: [GamepadButton; 4] = [
+        GamepadButton::South,
+        GamepadButton::East,
+        GamepadButton::West,
+        GamepadButton::North,
+    ];
+
+    fn apply(convention: FaceButtonConvention, layout: FaceLayout) -> Vec<GamepadButton> {
+        FACE.into_iter()
+            .map(|button| convention.apply(layout, button))
+            .collect()
+    }
+
+    #[test]
+    fn standard_layout_unchanged() {
+        for convention in [
+            FaceButtonConvention::Positional,
+            FaceButtonConvention::Labelled,
+        ] {
+            assert_eq!(apply(convention, FaceLayout::Standard), FACE);
+        }
+        assert_eq!(
+            apply(FaceButtonConvention::Positional, FaceLayout::Nintendo),
+            FACE
+        );
+    }
+
+    #[test]
+    fn nintendo_labelled_swapped() {
+        assert_eq!(
+            apply(FaceButtonConvention::Labelled, FaceLayout::Nintendo),
+            vec![
+                GamepadButton::East,
+                GamepadButton::South,
+                GamepadButton::North,
+                GamepadButton::West,
+            ]
+        );
+
+        // The swap is its own inverse, and leaves other buttons alone
+        for button in GamepadButton::all() {
+            let swapped = FaceButtonConvention::Labelled.apply(FaceLayout::Nintendo, button);
+            assert_eq!(
+                FaceButtonConvention::Labelled.apply(FaceLayout::Nintendo, swapped),
+                button
+            );
+            if !FACE.contains(&button) {
+                assert_eq!(swapped, button);
+            }
+        }
+    }
 }
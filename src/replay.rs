@@ -0,0 +1,471 @@
+//! Recording of the platform event stream to a file, and playback through [`crate::platform::ReplayPlatform`].
+//!
+//! Recordings are tab separated text with one event per line, prefixed with the time in microseconds since
+//! the start of the recording. Lines starting with `#` are comments. Backslashes, tabs and line breaks in
+//! names and messages are escaped as `\\`, `\t`, `\n` and `\r`. Errors are written with their kind and
+//! fields, so playback sends the same [`crate::GamepadErrorEvent`].
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
+
+use bevy_color::Srgba;
+use bevy_ecs::system::Resource;
+use bevy_input::gamepad::{GamepadAxis, GamepadButton, GamepadConnection};
+
+use crate::{
+    GamepadDeviceInfo,
+    error::GamepadError,
+    glyph::{ButtonLabel, ButtonLabels, intern_str},
+    platform::GamepadPlatformEvent,
+    profile::{ButtonChange, DPadChange, FaceLayout, GamepadChange, JoyConSide},
+    timestamp::InputTimestamp,
+};
+
+const HEADER: &str = "# bevy_gamepad recording v1";
+
+/// Records every platform event received by the plugin, with timestamps.
+///
+/// Insert this resource to start recording. The file is flushed when the resource is dropped.
+///
+/// ```ignore
+/// app.insert_resource(GamepadRecorder::create("session.gamepad")?);
+/// ```
+#[derive(Resource)]
+pub struct GamepadRecorder {
+    writer: Box<dyn Write + Send + Sync>,
+    start: InputTimestamp,
+
+    /// Time of the last recorded line, which keeps the recording in channel order
+    last: Duration,
+}
+
+impl GamepadRecorder {
+    /// Record to a new file, truncating any existing file
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, GamepadError> {
+        let file = File::create(path)?;
+        Self::new(BufWriter::new(file))
+    }
+
+    /// Record to any writer
+    pub fn new(writer: impl Write + Send + Sync + 'static) -> Result<Self, GamepadError> {
+        let mut writer: Box<dyn Write + Send + Sync> = Box::new(writer);
+        writeln!(writer, "{HEADER}")?;
+
+        Ok(Self {
+            writer,
+            start: InputTimestamp::now(),
+            last: Duration::ZERO,
+        })
+    }
+
+    /// Append an event to the recording, stamped with the time since the recording started.
+    /// Input changes are stamped with their capture time, other events with the time they are recorded.
+    pub(crate) fn record(&mut self, event: &GamepadPlatformEvent) -> Result<(), GamepadError> {
+        let time = match event {
+            GamepadPlatformEvent::InputChanged { timestamp, .. } => timestamp.captured(),
+            _ => InputTimestamp::now().captured(),
+        };
+        // Other events are stamped when drained, later than the capture time of inputs queued behind them.
+        // Never go back in time, so the recording keeps the order the events were received in.
+        self.last = self.last.max(time.saturating_sub(self.start.captured()));
+        let line = format_event(self.last, event);
+        Ok(writeln!(self.writer, "{line}")?)
+    }
+}
+
+/// A recording loaded for playback.
+///
+/// When this resource is inserted before adding the [`crate::GamepadPlugin`], the plugin replays the
+/// recording through [`crate::platform::ReplayPlatform`] instead of using the platform gamepad driver.
+/// Events are released when the elapsed [`bevy_time::Time`] reaches their timestamp, so playback is
+/// deterministic with a fixed [`bevy_time::TimeUpdateStrategy`].
+#[derive(Resource, Debug, Default)]
+pub struct GamepadReplay {
+    pub(crate) events: Vec<(Duration, GamepadPlatformEvent)>,
+}
+
+impl GamepadReplay {
+    /// Load a recording made by [`GamepadRecorder`]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, GamepadError> {
+        let file = File::open(path)?;
+        Self::read(BufReader::new(file))
+    }
+
+    /// Load a recording from any reader. Custom [`ButtonLabels`] in the recording are kept for the rest
+    /// of the program, as label tables are `'static`, once for each distinct table.
+    pub fn read(reader: impl BufRead) -> Result<Self, GamepadError> {
+        let mut events = Vec::new();
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let event = parse_event(&line).ok_or_else(|| {
+                GamepadError::Decode(format!("invalid recording line {}: {line}", number + 1))
+            })?;
+            events.push(event);
+        }
+
+        events.sort_by_key(|(time, _)| *time);
+
+        Ok(Self { events })
+    }
+
+    /// Number of events in the recording
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+fn format_event(time: Duration, event: &GamepadPlatformEvent) -> String {
+    let fields = match event {
+        GamepadPlatformEvent::Error(error) => format!("error\t{}", format_error(error)),
+
+        GamepadPlatformEvent::Connected {
+            id,
+            connection,
+            info,
+            labels,
+        } => {
+            let name = match connection {
+                GamepadConnection::Connected { name, .. } => name.as_str(),
+                GamepadConnection::Disconnected => "",
+            };
+            let face_layout = match info.face_layout() {
+                FaceLayout::Standard => "standard",
+                FaceLayout::Nintendo => "nintendo",
+            };
+            let joy_con = match info.joy_con() {
+                Some(JoyConSide::Left) => "left",
+                Some(JoyConSide::Right) => "right",
+                None => "-",
+            };
+            // Custom label tables are written out after the name, a field for each part of a label
+            let line = format!(
+                "connected\t{id}\t{}\t{face_layout}\t{joy_con}\t{}\t{}",
+                escape(info.profile()),
+                labels.name().unwrap_or("custom"),
+                escape(name),
+            );
+            match labels.name() {
+                Some(_) => line,
+                None => labels.iter().fold(line, |line, (button, label)| {
+                    let color = label.color.map_or_else(
+                        || String::from("-"),
+                        |color| {
+                            format!(
+                                "{},{},{},{}",
+                                color.red, color.green, color.blue, color.alpha
+                            )
+                        },
+                    );
+                    format!(
+                        "{line}\t{button:?}\t{}\t{}\t{color}",
+                        escape(label.name),
+                        escape(label.glyph)
+                    )
+                }),
+            }
+        }
+
+        GamepadPlatformEvent::Disconnected { id } => format!("disconnected\t{id}"),
+
+        GamepadPlatformEvent::InputChanged { id, change, .. } => match change {
+            GamepadChange::Button(change) => {
+                format!("button\t{id}\t{:?}\t{}", change.button(), change.value())
+            }
+            GamepadChange::Buttons(changes) => changes
+                .iter()
+                .fold(format!("buttons\t{id}"), |line, change| {
+                    format!("{line}\t{:?}\t{}", change.button(), change.value())
+                }),
+            GamepadChange::DualAxis {
+                x_axis,
+                x_value,
+                y_axis,
+                y_value,
+            } => format!("dual_axis\t{id}\t{x_axis:?}\t{x_value}\t{y_axis:?}\t{y_value}"),
+            GamepadChange::SingleAxis { axis, value } => {
+                format!("single_axis\t{id}\t{axis:?}\t{value}")
+            }
+            GamepadChange::DPad(dpad) => format!(
+                "dpad\t{id}\t{}\t{}\t{}\t{}",
+                dpad.up(),
+                dpad.down(),
+                dpad.left(),
+                dpad.right()
+            ),
+        },
+    };
+
+    format!("{}\t{fields}", time.as_micros())
+}
+
+fn parse_event(line: &str) -> Option<(Duration, GamepadPlatformEvent)> {
+    let mut fields = line.split('\t');
+    let time = Duration::from_micros(fields.next()?.parse().ok()?);
+    let kind = fields.next()?;
+
+    if kind == "error" {
+        return Some((time, GamepadPlatformEvent::Error(parse_error(fields)?)));
+    }
+
+    let id = fields.next()?.parse().ok()?;
+
+    let event = match kind {
+        "connected" => {
+            let profile = unescape(fields.next()?);
+            let face_layout = match fields.next()? {
+                "standard" => FaceLayout::Standard,
+                "nintendo" => FaceLayout::Nintendo,
+                _ => return None,
+            };
+            let joy_con = match fields.next()? {
+                "left" => Some(JoyConSide::Left),
+                "right" => Some(JoyConSide::Right),
+                _ => None,
+            };
+            let labels = fields.next()?;
+            let name = unescape(fields.next().unwrap_or_default());
+            let labels = match labels {
+                "custom" => parse_labels(&mut fields)?,
+                labels => ButtonLabels::from_name(labels)?,
+            };
+
+            GamepadPlatformEvent::Connected {
+                id,
+                connection: GamepadConnection::Connected {
+                    name,
+                    vendor_id: None,
+                    product_id: None,
+                },
+                info: GamepadDeviceInfo::new(profile, face_layout).with_joy_con(joy_con),
+                labels,
+            }
+        }
+
+        "disconnected" => GamepadPlatformEvent::Disconnected { id },
+
+        "button" => {
+            let button = parse_button(fields.next()?)?;
+            let change = ButtonChange::new(button, fields.next()?.parse().ok()?);
+            GamepadPlatformEvent::InputChanged {
+                id,
+                timestamp: InputTimestamp::from_captured(time),
+                change: GamepadChange::Button(change),
+            }
+        }
+
+        "buttons" => {
+            let mut changes = Vec::new();
+            while let Some(button) = fields.next() {
+                let button = parse_button(button)?;
+                changes.push(ButtonChange::new(button, fields.next()?.parse().ok()?));
+            }
+            GamepadPlatformEvent::InputChanged {
+                id,
+                timestamp: InputTimestamp::from_captured(time),
+                change: GamepadChange::Buttons(changes),
+            }
+        }
+
+        "dual_axis" => {
+            let x_axis = parse_axis(fields.next()?)?;
+            let x_value = fields.next()?.parse().ok()?;
+            let y_axis = parse_axis(fields.next()?)?;
+            let y_value = fields.next()?.parse().ok()?;
+            GamepadPlatformEvent::InputChanged {
+                id,
+                timestamp: InputTimestamp::from_captured(time),
+                change: GamepadChange::DualAxis {
+                    x_axis,
+                    x_value,
+                    y_axis,
+                    y_value,
+                },
+            }
+        }
+
+        "single_axis" => {
+            let axis = parse_axis(fields.next()?)?;
+            let value = fields.next()?.parse().ok()?;
+            GamepadPlatformEvent::InputChanged {
+                id,
+                timestamp: InputTimestamp::from_captured(time),
+                change: GamepadChange::SingleAxis { axis, value },
+            }
+        }
+
+        "dpad" => {
+            let mut value = || fields.next()?.parse::<f32>().ok();
+            let dpad = DPadChange::new(value()?, value()?, value()?, value()?);
+            GamepadPlatformEvent::InputChanged {
+                id,
+                timestamp: InputTimestamp::from_captured(time),
+                change: GamepadChange::DPad(dpad),
+            }
+        }
+
+        _ => return None,
+    };
+
+    Some((time, event))
+}
+
+/// The kind of an error followed by its fields
+fn format_error(error: &GamepadError) -> String {
+    match error {
+        GamepadError::Platform(message) => format!("platform\t{}", escape(message)),
+        GamepadError::DeviceInit { id, reason } => {
+            format!("device_init\t{id}\t{}", escape(reason))
+        }
+        GamepadError::UnsupportedController { id, name } => format!(
+            "unsupported_controller\t{}\t{}",
+            id.map_or_else(|| String::from("-"), |id| id.to_string()),
+            escape(name)
+        ),
+        GamepadError::OutputUnsupported { id, command } => {
+            format!("output_unsupported\t{id}\t{}", escape(command))
+        }
+        GamepadError::ChannelClosed => String::from("channel_closed"),
+        GamepadError::MappingParse(message) => format!("mapping_parse\t{}", escape(message)),
+        GamepadError::Decode(message) => format!("decode\t{}", escape(message)),
+        GamepadError::Encode(message) => format!("encode\t{}", escape(message)),
+        GamepadError::Io(message) => format!("io\t{}", escape(message)),
+    }
+}
+
+/// Reverse [`format_error`]
+fn parse_error<'a>(mut fields: impl Iterator<Item = &'a str>) -> Option<GamepadError> {
+    let kind = fields.next()?;
+    let error = match kind {
+        "platform" => GamepadError::Platform(unescape(fields.next()?)),
+        "device_init" => GamepadError::DeviceInit {
+            id: fields.next()?.parse().ok()?,
+            reason: unescape(fields.next()?),
+        },
+        "unsupported_controller" => GamepadError::UnsupportedController {
+            id: match fields.next()? {
+                "-" => None,
+                id => Some(id.parse().ok()?),
+            },
+            name: unescape(fields.next()?),
+        },
+        "output_unsupported" => GamepadError::OutputUnsupported {
+            id: fields.next()?.parse().ok()?,
+            command: unescape(fields.next()?),
+        },
+        "channel_closed" => GamepadError::ChannelClosed,
+        "mapping_parse" => GamepadError::MappingParse(unescape(fields.next()?)),
+        "decode" => GamepadError::Decode(unescape(fields.next()?)),
+        "encode" => GamepadError::Encode(unescape(fields.next()?)),
+        "io" => GamepadError::Io(unescape(fields.next()?)),
+
+        // Recordings made before errors were written with their kind only have the message
+        message => {
+            let message = [message].into_iter().chain(fields).collect::<Vec<_>>();
+            return Some(GamepadError::Platform(unescape(&message.join("\t"))));
+        }
+    };
+    Some(error)
+}
+
+/// Parse a custom label table, interned as label tables are `'static`
+fn parse_labels<'a>(fields: &mut impl Iterator<Item = &'a str>) -> Option<ButtonLabels> {
+    let mut labels = Vec::new();
+    while let Some(button) = fields.next() {
+        let button = parse_button(button)?;
+        let name = intern_str(unescape(fields.next()?));
+        let glyph = intern_str(unescape(fields.next()?));
+        let label = match fields.next()? {
+            "-" => ButtonLabel::new(name, glyph),
+            color => {
+                let mut channels = color.split(',').map(|channel| channel.parse().ok());
+                let mut channel = || channels.next().flatten();
+                let color = Srgba::new(channel()?, channel()?, channel()?, channel()?);
+                ButtonLabel::new(name, glyph).with_color(color)
+            }
+        };
+        labels.push((button, label));
+    }
+
+    Some(ButtonLabels::intern(labels))
+}
+
+/// Escape a text field, so the tabs and line breaks in it do not split the line
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reverse [`escape`]. Other backslashes are kept as they are, as in recordings made before escaping.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Parse the [`std::fmt::Debug`] representation of a [`GamepadButton`]
+fn parse_button(name: &str) -> Option<GamepadButton> {
+    if let Some(other) = name
+        .strip_prefix("Other(")
+        .and_then(|n| n.strip_suffix(')'))
+    {
+        return other.parse().ok().map(GamepadButton::Other);
+    }
+
+    GamepadButton::all()
+        .into_iter()
+        .find(|button| format!("{button:?}") == name)
+}
+
+/// Parse the [`std::fmt::Debug`] representation of a [`GamepadAxis`]
+fn parse_axis(name: &str) -> Option<GamepadAxis> {
+    if let Some(other) = name
+        .strip_prefix("Other(")
+        .and_then(|n| n.strip_suffix(')'))
+    {
+        return other.parse().ok().map(GamepadAxis::Other);
+    }
+
+    GamepadAxis::all()
+        .into_iter()
+        .find(|axis| format!("{axis:?}") == name)
+}
@@ -0,0 +1,87 @@
+use bevy_ecs::prelude::*;
+use bevy_utils::HashSet;
+
+use crate::{
+    GamepadErrorEvent, GamepadId, GamepadPlatformHandler,
+    platform::{GamepadPlatformEvent, Platform, PlatformDriver},
+    timestamp::InputTimestamp,
+};
+
+/// Emit the full state of gamepads read from their devices, correcting inputs the game may have
+/// missed. Gamepads are resynced when they connect and when the app regains focus.
+///
+/// ```ignore
+/// fn resume(mut resync: EventWriter<GamepadResync>) {
+///     resync.send(GamepadResync::all());
+/// }
+/// ```
+#[derive(Event, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadResync {
+    /// Gamepad entity to resync, or every connected gamepad if `None`
+    pub gamepad: Option<Entity>,
+}
+
+impl GamepadResync {
+    /// Resync every connected gamepad
+    pub fn all() -> Self {
+        Self { gamepad: None }
+    }
+
+    /// Resync the devices routed to a gamepad entity
+    pub fn gamepad(gamepad: Entity) -> Self {
+        Self {
+            gamepad: Some(gamepad),
+        }
+    }
+}
+
+/// Read the state of the devices to resync, queueing it as input for the platform system
+pub(crate) fn resync_system<P: Platform>(
+    mut requests: EventReader<GamepadResync>,
+    mut driver: NonSendMut<PlatformDriver<P>>,
+    mut handler: ResMut<GamepadPlatformHandler>,
+    mut error_events: EventWriter<GamepadErrorEvent>,
+) {
+    let mut ids = HashSet::<GamepadId>::default();
+    for request in requests.read() {
+        ids.extend(
+            handler
+                .devices
+                .iter()
+                .filter(|(id, device)| match request.gamepad {
+                    Some(gamepad) => {
+                        device.entity == gamepad || handler.index.get(*id) == Some(&gamepad)
+                    }
+                    None => true,
+                })
+                .map(|(id, _)| *id),
+        );
+    }
+
+    let Some(driver) = driver.get_mut() else {
+        return;
+    };
+
+    for id in ids {
+        match driver.snapshot(id) {
+            Ok(snapshot) => {
+                let timestamp = InputTimestamp::now();
+                handler.resync.extend(snapshot.into_iter().map(|change| {
+                    GamepadPlatformEvent::InputChanged {
+                        id,
+                        change,
+                        timestamp,
+                    }
+                }));
+            }
+            Err(error) => {
+                bevy_log::error!("Failed to resync gamepad {id}: {error}");
+                error_events.send(GamepadErrorEvent {
+                    gamepad: handler.index.get(&id).copied(),
+                    error,
+                });
+            }
+        }
+    }
+}
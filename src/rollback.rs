@@ -0,0 +1,280 @@
+//! Compact gamepad state for rollback netcode.
+//!
+//! A [`GamepadSnapshot`] packs the state of a gamepad into 18 bytes: a button bitfield, the stick axes
+//! quantized to 16 bits and the analog triggers quantized to 8 bits. Snapshots are `Copy` and `Hash`, so they
+//! can be stored per frame, sent to peers, and compared or checksummed when confirmed input arrives.
+//!
+//! Insert a [`GamepadSnapshot`] on a gamepad entity and it is kept up to date with every input the plugin
+//! emits. Copy it into the input of the simulation frame once per frame.
+//!
+//! Quantization is deterministic: the same input value always packs to the same bits on every machine.
+//! Game logic should only read the quantized values through the snapshot, so every peer simulates with
+//! identical input.
+//!
+//! ```ignore
+//! commands.entity(gamepad).insert(GamepadSnapshot::default());
+//!
+//! fn collect(snapshots: Query<&GamepadSnapshot>, mut session: ResMut<Session>) {
+//!     for snapshot in snapshots.iter() {
+//!         session.add_local_input(snapshot.to_bytes());
+//!     }
+//! }
+//! ```
+
+use bevy_ecs::component::Component;
+use bevy_input::gamepad::{GamepadAxis, GamepadButton, GamepadInput};
+
+use crate::history::PRESS_THRESHOLD;
+
+/// Bit of each button in [`GamepadSnapshot::buttons`]. The order is part of the wire format and must not change.
+const BUTTONS: [GamepadButton; 19] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::North,
+    GamepadButton::West,
+    GamepadButton::C,
+    GamepadButton::Z,
+    GamepadButton::LeftTrigger,
+    GamepadButton::LeftTrigger2,
+    GamepadButton::RightTrigger,
+    GamepadButton::RightTrigger2,
+    GamepadButton::Select,
+    GamepadButton::Start,
+    GamepadButton::Mode,
+    GamepadButton::LeftThumb,
+    GamepadButton::RightThumb,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+];
+
+/// Slot of each axis in [`GamepadSnapshot::axes`]. The order is part of the wire format and must not change.
+const AXES: [GamepadAxis; 6] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::LeftZ,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+    GamepadAxis::RightZ,
+];
+
+/// Quantize an axis value in `-1.0..=1.0` to 16 bits
+pub fn quantize_axis(value: f32) -> i16 {
+    if value.is_nan() {
+        return 0;
+    }
+    (value.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16
+}
+
+/// Value of an axis quantized with [`quantize_axis`]
+pub fn dequantize_axis(value: i16) -> f32 {
+    (value as f32 / i16::MAX as f32).max(-1.0)
+}
+
+/// Quantize an analog trigger value in `0.0..=1.0` to 8 bits
+pub fn quantize_trigger(value: f32) -> u8 {
+    if value.is_nan() {
+        return 0;
+    }
+    (value.clamp(0.0, 1.0) * u8::MAX as f32).round() as u8
+}
+
+/// Value of a trigger quantized with [`quantize_trigger`]
+pub fn dequantize_trigger(value: u8) -> f32 {
+    value as f32 / u8::MAX as f32
+}
+
+/// How to predict the input of a remote player for frames whose input has not arrived yet
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum Prediction {
+    /// Repeat the last confirmed input. Right most of the time, since input rarely changes between frames.
+    #[default]
+    RepeatLast,
+
+    /// Keep the axes, but predict every button as released, to avoid predicting actions the player did not take
+    ReleaseButtons,
+
+    /// Predict no input at all
+    Neutral,
+}
+
+/// State of a gamepad packed for rollback netcode. See the [module documentation](self).
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadSnapshot {
+    /// One bit per button, pressed at the default `bevy_input` press threshold
+    pub buttons: u32,
+
+    /// Quantized sticks and Z axes
+    pub axes: [i16; 6],
+
+    /// Quantized analog values of [`GamepadButton::LeftTrigger2`] and [`GamepadButton::RightTrigger2`]
+    pub triggers: [u8; 2],
+}
+
+impl GamepadSnapshot {
+    /// Size of the encoding of [`Self::to_bytes`]
+    pub const SIZE: usize = 18;
+
+    pub fn pressed(&self, button: GamepadButton) -> bool {
+        button_bit(button).is_some_and(|bit| self.buttons & bit != 0)
+    }
+
+    /// The button is pressed in this snapshot but was not in the `previous` one
+    pub fn just_pressed(&self, previous: &Self, button: GamepadButton) -> bool {
+        self.pressed(button) && !previous.pressed(button)
+    }
+
+    /// The button was pressed in the `previous` snapshot but is not in this one
+    pub fn just_released(&self, previous: &Self, button: GamepadButton) -> bool {
+        !self.pressed(button) && previous.pressed(button)
+    }
+
+    /// Pressed buttons, in bit order
+    pub fn get_pressed(&self) -> impl Iterator<Item = GamepadButton> + '_ {
+        BUTTONS.into_iter().filter(|button| self.pressed(*button))
+    }
+
+    /// Dequantized value of an axis
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        AXES.iter()
+            .position(|slot| *slot == axis)
+            .map_or(0.0, |slot| dequantize_axis(self.axes[slot]))
+    }
+
+    /// Dequantized analog value of a trigger, or the pressed state of any other button as 0 or 1
+    pub fn button_value(&self, button: GamepadButton) -> f32 {
+        match button {
+            GamepadButton::LeftTrigger2 => dequantize_trigger(self.triggers[0]),
+            GamepadButton::RightTrigger2 => dequantize_trigger(self.triggers[1]),
+            button if self.pressed(button) => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Predict the input of the next frame from this confirmed snapshot
+    pub fn predict(&self, prediction: Prediction) -> Self {
+        match prediction {
+            Prediction::RepeatLast => *self,
+            Prediction::ReleaseButtons => Self {
+                buttons: 0,
+                triggers: [0; 2],
+                ..*self
+            },
+            Prediction::Neutral => Self::default(),
+        }
+    }
+
+    /// Apply a change of a button or axis, as emitted by the plugin
+    pub fn apply(&mut self, input: GamepadInput, value: f32) {
+        match input {
+            GamepadInput::Button(button) => {
+                if let Some(bit) = button_bit(button) {
+                    if value >= PRESS_THRESHOLD {
+                        self.buttons |= bit;
+                    } else {
+                        self.buttons &= !bit;
+                    }
+                }
+                match button {
+                    GamepadButton::LeftTrigger2 => self.triggers[0] = quantize_trigger(value),
+                    GamepadButton::RightTrigger2 => self.triggers[1] = quantize_trigger(value),
+                    _ => {}
+                }
+            }
+            GamepadInput::Axis(axis) => {
+                if let Some(slot) = AXES.iter().position(|slot| *slot == axis) {
+                    self.axes[slot] = quantize_axis(value);
+                }
+            }
+        }
+    }
+
+    /// Encode in a fixed size little endian form
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..4].copy_from_slice(&self.buttons.to_le_bytes());
+        for (slot, axis) in self.axes.iter().enumerate() {
+            bytes[4 + slot * 2..6 + slot * 2].copy_from_slice(&axis.to_le_bytes());
+        }
+        bytes[16..18].copy_from_slice(&self.triggers);
+        bytes
+    }
+
+    /// Decode the form written by [`Self::to_bytes`]
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        let mut axes = [0; 6];
+        for (slot, axis) in axes.iter_mut().enumerate() {
+            *axis = i16::from_le_bytes([bytes[4 + slot * 2], bytes[5 + slot * 2]]);
+        }
+
+        Self {
+            buttons: u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            axes,
+            triggers: [bytes[16], bytes[17]],
+        }
+    }
+}
+
+fn button_bit(button: GamepadButton) -> Option<u32> {
+    BUTTONS
+        .iter()
+        .position(|slot| *slot == button)
+        .map(|index| 1 << index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(inputs: &[(GamepadInput, f32)]) -> GamepadSnapshot {
+        let mut snapshot = GamepadSnapshot::default();
+        for (input, value) in inputs {
+            snapshot.apply(*input, *value);
+        }
+        snapshot
+    }
+
+    const INPUTS: [(GamepadInput, f32); 5] = [
+        (GamepadInput::Button(GamepadButton::South), 1.0),
+        (GamepadInput::Button(GamepadButton::DPadRight), 1.0),
+        (GamepadInput::Button(GamepadButton::RightTrigger2), 0.37),
+        (GamepadInput::Axis(GamepadAxis::LeftStickX), -0.61),
+        (GamepadInput::Axis(GamepadAxis::RightStickY), 0.2),
+    ];
+
+    #[test]
+    fn axis_quantization_is_stable() {
+        for quantized in -i16::MAX..=i16::MAX {
+            assert_eq!(quantize_axis(dequantize_axis(quantized)), quantized);
+        }
+        assert_eq!(quantize_axis(dequantize_axis(i16::MIN)), -i16::MAX);
+    }
+
+    #[test]
+    fn trigger_quantization_is_stable() {
+        for quantized in u8::MIN..=u8::MAX {
+            assert_eq!(quantize_trigger(dequantize_trigger(quantized)), quantized);
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let snapshot = snapshot(&INPUTS);
+        assert_eq!(GamepadSnapshot::from_bytes(snapshot.to_bytes()), snapshot);
+    }
+
+    #[test]
+    fn identical_inputs_are_equal() {
+        let first = snapshot(&INPUTS);
+        let second = snapshot(&INPUTS);
+        assert_eq!(first, second);
+        assert_eq!(first.to_bytes(), second.to_bytes());
+
+        let mut changed = INPUTS;
+        changed[3].1 = -0.62;
+        assert_ne!(snapshot(&changed), first);
+    }
+}
@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use bevy_ecs::system::Resource;
+
+use crate::{
+    disconnect::DisconnectPolicy,
+    focus::FocusPolicy,
+    profile::{FaceButtonConvention, TriggerReporting},
+};
+
+/// Global settings of the [`crate::GamepadPlugin`].
+///
+/// Insert before adding the plugin to override the defaults, or modify at runtime.
+#[derive(Resource, Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadPluginSettings {
+    /// Face button convention for Nintendo layout controllers, unless overridden
+    /// by a [`FaceButtonConvention`] component on the gamepad entity
+    pub face_buttons: FaceButtonConvention,
+
+    /// Whether triggers are reported as buttons, axes or both, for every controller
+    pub triggers: TriggerReporting,
+
+    /// Keep only the latest value of each axis received in a frame. Button changes are never coalesced.
+    pub coalesce_axes: bool,
+
+    /// Soft cap on the input events waiting in the channel from the platform driver, no cap if `None`. The
+    /// channel stays unbounded: input sent while the cap is reached is dropped and counted in
+    /// [`crate::GamepadChannelStats`], while connections, disconnections and errors are never dropped nor
+    /// counted against the cap. Read when the plugin is built.
+    pub channel_capacity: Option<usize>,
+
+    /// How long input of a device is held while waiting for its connect notification, before it is dropped
+    /// and counted in [`crate::GamepadChannelStats::orphaned`]
+    pub orphan_timeout: Duration,
+
+    /// What happens to the entity of a gamepad when its device disconnects
+    pub disconnect_policy: DisconnectPolicy,
+
+    /// What happens to input while no window of the app is focused
+    pub focus_policy: FocusPolicy,
+}
+
+impl Default for GamepadPluginSettings {
+    fn default() -> Self {
+        Self {
+            face_buttons: FaceButtonConvention::default(),
+            triggers: TriggerReporting::default(),
+            coalesce_axes: false,
+            channel_capacity: None,
+            orphan_timeout: Duration::from_secs(1),
+            disconnect_policy: DisconnectPolicy::default(),
+            focus_policy: FocusPolicy::default(),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use bevy_ecs::prelude::*;
+use bevy_input::gamepad::{
+    GamepadAxis, GamepadButton, GamepadConnection, GamepadConnectionEvent, GamepadInput,
+};
+use bevy_utils::HashSet;
+
+use crate::{DPadAsAxis, Disconnected, GamepadPlatformHandler};
+
+/// Splits one gamepad into several logical gamepads, each receiving a subset of the buttons and axes.
+///
+/// Insert this component on a connected gamepad entity. An entity is spawned and announced as a connected
+/// gamepad for each part, and the original gamepad is disconnected while split. Inputs which are not part
+/// of any part are dropped. Removing the component despawns the parts and reconnects the original gamepad.
+/// The parts disconnect with the device, following the [`crate::DisconnectPolicy`], and are connected
+/// again in place of the original gamepad when the device reconnects.
+///
+/// ```ignore
+/// // One player on the left stick and D-pad, the other on the right stick and face buttons
+/// commands.entity(gamepad).insert(GamepadSplit::left_right());
+/// ```
+#[derive(Component, Debug, Clone, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadSplit {
+    #[cfg_attr(feature = "serialize", serde(with = "crate::codec::input_sets"))]
+    parts: Vec<HashSet<GamepadInput>>,
+}
+
+impl GamepadSplit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a part receiving the given buttons and axes
+    pub fn with_part(mut self, inputs: impl IntoIterator<Item = GamepadInput>) -> Self {
+        self.parts.push(inputs.into_iter().collect());
+        self
+    }
+
+    /// Split into a left part with the left stick, D-pad including [`DPadAsAxis`], left shoulder buttons and select, and a right part
+    /// with the right stick, face buttons, right shoulder buttons and start
+    pub fn left_right() -> Self {
+        Self::new()
+            .with_part([
+                GamepadAxis::LeftStickX.into(),
+                GamepadAxis::LeftStickY.into(),
+                GamepadAxis::LeftZ.into(),
+                GamepadButton::DPadUp.into(),
+                GamepadButton::DPadDown.into(),
+                GamepadButton::DPadLeft.into(),
+                GamepadButton::DPadRight.into(),
+                DPadAsAxis::X.into(),
+                DPadAsAxis::Y.into(),
+                GamepadButton::LeftTrigger.into(),
+                GamepadButton::LeftTrigger2.into(),
+                GamepadButton::LeftThumb.into(),
+                GamepadButton::Select.into(),
+            ])
+            .with_part([
+                GamepadAxis::RightStickX.into(),
+                GamepadAxis::RightStickY.into(),
+                GamepadAxis::RightZ.into(),
+                GamepadButton::South.into(),
+                GamepadButton::East.into(),
+                GamepadButton::North.into(),
+                GamepadButton::West.into(),
+                GamepadButton::RightTrigger.into(),
+                GamepadButton::RightTrigger2.into(),
+                GamepadButton::RightThumb.into(),
+                GamepadButton::Start.into(),
+            ])
+    }
+
+    pub fn parts(&self) -> &[HashSet<GamepadInput>] {
+        &self.parts
+    }
+}
+
+/// Routing of a split gamepad kept in [`GamepadPlatformHandler`]
+pub(crate) struct SplitState {
+    /// Spawned entity of each part, with the inputs routed to it
+    parts: Vec<(Entity, HashSet<GamepadInput>)>,
+
+    /// Connection of the split gamepad, to reconnect it when the split is removed
+    connection: Option<GamepadConnection>,
+}
+
+impl SplitState {
+    /// The part entity an input of the split gamepad is routed to
+    pub(crate) fn route(&self, input: GamepadInput) -> Option<Entity> {
+        self.parts
+            .iter()
+            .find(|(_, inputs)| inputs.contains(&input))
+            .map(|(entity, _)| *entity)
+    }
+
+    /// Entities of the parts
+    pub(crate) fn parts(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.parts.iter().map(|(entity, _)| *entity)
+    }
+
+    /// Announce the parts again when the device of the split gamepad reconnects
+    pub(crate) fn reconnect(
+        &mut self,
+        commands: &mut Commands,
+        connection: GamepadConnection,
+        connection_writer: &mut EventWriter<GamepadConnectionEvent>,
+    ) {
+        for (i, (part, _)) in self.parts.iter().enumerate() {
+            commands.entity(*part).remove::<Disconnected>();
+            connection_writer.send(GamepadConnectionEvent {
+                gamepad: *part,
+                connection: part_connection(Some(&connection), i),
+            });
+        }
+        self.connection = Some(connection);
+    }
+}
+
+/// Connection announced for a part, named after the split gamepad
+fn part_connection(connection: Option<&GamepadConnection>, index: usize) -> GamepadConnection {
+    let name = match connection {
+        Some(GamepadConnection::Connected { name, .. }) => name.as_str(),
+        _ => "Gamepad",
+    };
+
+    GamepadConnection::Connected {
+        name: format!("{name} ({})", index + 1),
+        vendor_id: None,
+        product_id: None,
+    }
+}
+
+/// Spawn the parts of newly split gamepads, and restore gamepads when the split is removed
+pub(crate) fn split_system(
+    mut commands: Commands,
+    mut handler: ResMut<GamepadPlatformHandler>,
+    added: Query<(Entity, &GamepadSplit), Added<GamepadSplit>>,
+    mut removed: RemovedComponents<GamepadSplit>,
+    entities: Query<Entity>,
+    mut connection_writer: EventWriter<GamepadConnectionEvent>,
+) {
+    for entity in removed.read() {
+        let Some(state) = handler.splits.remove(&entity) else {
+            continue;
+        };
+
+        for (part, _) in state.parts {
+            commands.entity(part).despawn();
+        }
+
+        if let (Some(connection), true) = (state.connection, entities.contains(entity)) {
+            connection_writer.send(GamepadConnectionEvent {
+                gamepad: entity,
+                connection,
+            });
+        }
+    }
+
+    for (entity, split) in added.iter() {
+        let connection = handler
+            .devices
+            .values()
+            .find(|device| device.entity == entity)
+            .map(|device| device.connection.clone());
+
+        let parts = split
+            .parts()
+            .iter()
+            .enumerate()
+            .map(|(i, inputs)| {
+                let part = commands.spawn_empty().id();
+
+                connection_writer.send(GamepadConnectionEvent {
+                    gamepad: part,
+                    connection: part_connection(connection.as_ref(), i),
+                });
+
+                (part, inputs.clone())
+            })
+            .collect();
+
+        connection_writer.send(GamepadConnectionEvent {
+            gamepad: entity,
+            connection: GamepadConnection::Disconnected,
+        });
+
+        handler
+            .splits
+            .insert(entity, SplitState { parts, connection });
+    }
+}
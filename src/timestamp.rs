@@ -0,0 +1,91 @@
+use std::{
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use bevy_ecs::event::Event;
+use bevy_input::gamepad::RawGamepadEvent;
+
+/// Reference point of [`InputTimestamp::captured`], set the first time a timestamp is taken
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+
+fn epoch() -> Instant {
+    *EPOCH.get_or_init(Instant::now)
+}
+
+/// Set the epoch if it has not been set yet, so instants from the first frame onwards can be represented
+pub(crate) fn start_epoch() {
+    epoch();
+}
+
+/// Time at which an input was captured by the platform driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputTimestamp {
+    /// Monotonic time since the process wide epoch when the driver callback received the input
+    captured: Duration,
+
+    /// Timestamp reported by the device or backend, in the time base of the backend
+    device: Option<Duration>,
+}
+
+impl InputTimestamp {
+    /// Capture the current monotonic time
+    pub fn now() -> Self {
+        Self {
+            captured: epoch().elapsed(),
+            device: None,
+        }
+    }
+
+    /// A timestamp captured at the given offset from the process wide epoch
+    pub(crate) fn from_captured(captured: Duration) -> Self {
+        Self {
+            captured,
+            device: None,
+        }
+    }
+
+    /// A timestamp captured at the given instant, which must not be before the first frame of the app
+    pub(crate) fn from_instant(instant: Instant) -> Self {
+        Self::from_captured(instant.saturating_duration_since(epoch()))
+    }
+
+    /// Attach the timestamp reported by the device
+    pub fn with_device(self, device: Duration) -> Self {
+        Self {
+            device: Some(device),
+            ..self
+        }
+    }
+
+    /// Monotonic capture time, relative to a process wide epoch shared by all timestamps
+    pub fn captured(&self) -> Duration {
+        self.captured
+    }
+
+    /// Capture time as an [`Instant`]
+    pub fn instant(&self) -> Instant {
+        epoch() + self.captured
+    }
+
+    /// Timestamp reported by the device, if the backend provides one.
+    /// On Apple platforms this is the `lastEventTimestamp` of the controller profile, in system uptime.
+    pub fn device(&self) -> Option<Duration> {
+        self.device
+    }
+
+    /// Time elapsed since the input was captured
+    pub fn elapsed(&self) -> Duration {
+        epoch().elapsed().saturating_sub(self.captured)
+    }
+}
+
+/// Sent alongside each [`RawGamepadEvent`] emitted by the plugin, with the time its input was captured.
+/// Allows measuring input latency, and ordering inputs within a frame.
+#[derive(Event, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimestampedGamepadEvent {
+    pub event: RawGamepadEvent,
+    pub timestamp: InputTimestamp,
+}
@@ -0,0 +1,90 @@
+//! Setup shared by the integration tests. Every test binary compiles this module, so helpers which
+//! some of them do not use allow dead code.
+
+use std::time::Duration;
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_gamepad::{
+    ButtonChange, ButtonLabels, FaceLayout, GamepadChange, GamepadDeviceInfo, GamepadError,
+    GamepadId, GamepadPlugin, GamepadPluginSettings, VirtualGamepads,
+};
+use bevy_input::{
+    InputPlugin,
+    gamepad::{Gamepad, GamepadButton},
+};
+use bevy_time::{TimePlugin, TimeUpdateStrategy};
+
+/// Time advanced by each update
+pub const FRAME: Duration = Duration::from_millis(16);
+
+/// App with the time and input plugins, advancing by [`FRAME`] each update
+pub fn base_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((TimePlugin, InputPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+    app
+}
+
+/// App with the gamepad plugin driving the virtual gamepads
+#[allow(dead_code)]
+pub fn app_with(gamepads: &VirtualGamepads, settings: GamepadPluginSettings) -> App {
+    let mut app = base_app();
+    app.insert_resource(settings);
+    app.insert_resource(gamepads.clone());
+    app.add_plugins(GamepadPlugin);
+    app
+}
+
+/// App with the gamepad plugin driving new virtual gamepads
+#[allow(dead_code)]
+pub fn app(settings: GamepadPluginSettings) -> (App, VirtualGamepads) {
+    let gamepads = VirtualGamepads::default();
+    (app_with(&gamepads, settings), gamepads)
+}
+
+/// App with a virtual Xbox controller connected as platform id 0
+#[allow(dead_code)]
+pub fn connected_app(settings: GamepadPluginSettings) -> (App, VirtualGamepads) {
+    let (mut app, gamepads) = app(settings);
+    connect(&gamepads, 0).unwrap();
+    app.update();
+    (app, gamepads)
+}
+
+/// Connect a virtual Xbox controller
+#[allow(dead_code)]
+pub fn connect(gamepads: &VirtualGamepads, id: GamepadId) -> Result<(), GamepadError> {
+    gamepads.connect(
+        id,
+        "Virtual Xbox",
+        GamepadDeviceInfo::new("Xbox", FaceLayout::Standard),
+        ButtonLabels::XBOX,
+    )
+}
+
+/// Change the value of a button of a virtual gamepad
+#[allow(dead_code)]
+pub fn button(
+    gamepads: &VirtualGamepads,
+    id: GamepadId,
+    button: GamepadButton,
+    value: f32,
+) -> Result<(), GamepadError> {
+    gamepads.input(id, GamepadChange::Button(ButtonChange::new(button, value)))
+}
+
+/// Entity of the only connected gamepad
+#[allow(dead_code)]
+pub fn gamepad(app: &mut App) -> Entity {
+    app.world_mut()
+        .query_filtered::<Entity, With<Gamepad>>()
+        .single(app.world())
+}
+
+/// State of the only connected gamepad
+#[allow(dead_code)]
+pub fn state(app: &mut App) -> &Gamepad {
+    let entity = gamepad(app);
+    app.world().get::<Gamepad>(entity).unwrap()
+}
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use bevy_app::prelude::*;
+use bevy_diagnostic::{DiagnosticPath, DiagnosticsStore};
+use bevy_gamepad::{
+    DisconnectPolicy, GamepadChannelStats, GamepadDiagnosticsPlugin, GamepadPluginSettings,
+    VirtualGamepads,
+};
+use bevy_input::gamepad::GamepadButton;
+use bevy_time::TimeUpdateStrategy;
+
+mod common;
+
+fn app() -> (App, VirtualGamepads) {
+    let (mut app, gamepads) = common::app(GamepadPluginSettings {
+        disconnect_policy: DisconnectPolicy::Despawn,
+        ..Default::default()
+    });
+    app.add_plugins(GamepadDiagnosticsPlugin);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+        100,
+    )));
+
+    common::connect(&gamepads, 0).unwrap();
+    app.update();
+
+    (app, gamepads)
+}
+
+fn value(app: &App, path: &DiagnosticPath) -> Option<f64> {
+    app.world()
+        .resource::<DiagnosticsStore>()
+        .get_measurement(path)
+        .map(|measurement| measurement.value)
+}
+
+#[test]
+fn pipeline_diagnostics() {
+    let (mut app, gamepads) = app();
+    for value in [1.0, 0.0, 1.0] {
+        common::button(&gamepads, 0, GamepadButton::South, value).unwrap();
+    }
+    gamepads.unhandled().unwrap();
+    app.update();
+
+    let stats = app.world().resource::<GamepadChannelStats>();
+    assert_eq!(stats.frame_received(), 3);
+    assert_eq!(stats.depth(), 3);
+    assert_eq!(stats.unhandled(), 1);
+    assert!(stats.latency().is_some());
+
+    assert_eq!(
+        value(&app, &GamepadDiagnosticsPlugin::EVENTS_RECEIVED),
+        Some(3.0)
+    );
+    assert_eq!(
+        value(&app, &GamepadDiagnosticsPlugin::CHANNEL_DEPTH),
+        Some(3.0)
+    );
+    assert_eq!(
+        value(&app, &GamepadDiagnosticsPlugin::UNHANDLED_ELEMENTS),
+        Some(1.0)
+    );
+    assert!(value(&app, &GamepadDiagnosticsPlugin::LATENCY).is_some());
+
+    // 3 events in a frame of 100ms
+    let gamepad = common::gamepad(&mut app);
+    let rate = GamepadDiagnosticsPlugin::event_rate(gamepad);
+    assert_eq!(value(&app, &rate), Some(30.0));
+
+    app.update();
+    assert_eq!(
+        value(&app, &GamepadDiagnosticsPlugin::EVENTS_RECEIVED),
+        Some(0.0)
+    );
+    assert_eq!(value(&app, &rate), Some(0.0));
+}
+
+#[test]
+fn despawned_gamepad_rate_disabled() {
+    let (mut app, gamepads) = app();
+    common::button(&gamepads, 0, GamepadButton::South, 1.0).unwrap();
+    app.update();
+
+    let gamepad = common::gamepad(&mut app);
+    gamepads.disconnect(0).unwrap();
+    app.update();
+    app.update();
+
+    let store = app.world().resource::<DiagnosticsStore>();
+    let rate = store
+        .get(&GamepadDiagnosticsPlugin::event_rate(gamepad))
+        .unwrap();
+    assert!(!rate.is_enabled);
+}
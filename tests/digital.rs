@@ -0,0 +1,69 @@
+use bevy_app::prelude::*;
+use bevy_gamepad::{
+    AnalogInput, ButtonChange, DigitalThreshold, DigitalThresholds, GamepadChange, VirtualGamepads,
+};
+use bevy_input::gamepad::{GamepadAxis, GamepadButton};
+
+mod common;
+
+fn app(thresholds: DigitalThresholds) -> (App, VirtualGamepads) {
+    let (mut app, gamepads) = common::connected_app(Default::default());
+    let gamepad = common::gamepad(&mut app);
+    app.world_mut().entity_mut(gamepad).insert(thresholds);
+    (app, gamepads)
+}
+
+fn pressed(app: &mut App, gamepads: &VirtualGamepads, change: GamepadChange) -> bool {
+    gamepads.input(0, change).unwrap();
+    app.update();
+    common::state(app).pressed(DigitalThresholds::RIGHT_TRIGGER)
+}
+
+fn trigger(value: f32) -> GamepadChange {
+    GamepadChange::Button(ButtonChange::new(GamepadButton::RightTrigger2, value))
+}
+
+#[test]
+fn trigger_hysteresis() {
+    let (mut app, gamepads) = app(DigitalThresholds::default());
+
+    assert!(!pressed(&mut app, &gamepads, trigger(0.7)));
+    assert!(pressed(&mut app, &gamepads, trigger(0.8)));
+    // Between the thresholds the button stays pressed
+    assert!(pressed(&mut app, &gamepads, trigger(0.7)));
+    assert!(!pressed(&mut app, &gamepads, trigger(0.6)));
+}
+
+#[test]
+fn custom_threshold() {
+    let (mut app, gamepads) = app(DigitalThresholds::default().with_threshold(
+        AnalogInput::Button(GamepadButton::RightTrigger2),
+        DigitalThreshold::new(0.3, 0.2),
+    ));
+
+    assert!(pressed(&mut app, &gamepads, trigger(0.3)));
+    assert!(pressed(&mut app, &gamepads, trigger(0.25)));
+    assert!(!pressed(&mut app, &gamepads, trigger(0.2)));
+}
+
+#[test]
+fn stick_directions() {
+    let (mut app, gamepads) = app(DigitalThresholds::default());
+    gamepads
+        .input(
+            0,
+            GamepadChange::DualAxis {
+                x_axis: GamepadAxis::LeftStickX,
+                x_value: -0.9,
+                y_axis: GamepadAxis::LeftStickY,
+                y_value: 0.1,
+            },
+        )
+        .unwrap();
+    app.update();
+
+    let gamepad = common::state(&mut app);
+    assert!(gamepad.pressed(DigitalThresholds::LEFT_STICK_LEFT));
+    assert!(!gamepad.pressed(DigitalThresholds::LEFT_STICK_RIGHT));
+    assert!(!gamepad.pressed(DigitalThresholds::LEFT_STICK_UP));
+}
@@ -0,0 +1,105 @@
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_gamepad::{
+    DiscoveryEnd, DiscoveryFinished, DiscoveryStarted, GamepadDiscovery, GamepadDiscoveryStatus,
+};
+
+mod common;
+
+use common::FRAME;
+
+fn app() -> App {
+    common::app(Default::default()).0
+}
+
+fn events<E: Event + Clone>(app: &App) -> Vec<E> {
+    app.world()
+        .resource::<Events<E>>()
+        .iter_current_update_events()
+        .cloned()
+        .collect()
+}
+
+fn status(app: &App) -> &GamepadDiscoveryStatus {
+    app.world().resource::<GamepadDiscoveryStatus>()
+}
+
+#[test]
+fn start_and_stop() {
+    let mut app = app();
+    app.update();
+    assert!(!status(&app).is_active());
+
+    app.world_mut()
+        .send_event(GamepadDiscovery::Start { timeout: None });
+    app.update();
+    assert_eq!(
+        events::<DiscoveryStarted>(&app),
+        vec![DiscoveryStarted { timeout: None }]
+    );
+    assert!(status(&app).is_active());
+    assert_eq!(status(&app).remaining(), None);
+
+    app.world_mut().send_event(GamepadDiscovery::Stop);
+    app.update();
+    assert_eq!(
+        events::<DiscoveryFinished>(&app),
+        vec![DiscoveryFinished {
+            reason: DiscoveryEnd::Stopped
+        }]
+    );
+    assert!(!status(&app).is_active());
+}
+
+#[test]
+fn timeout() {
+    let mut app = app();
+    app.world_mut().send_event(GamepadDiscovery::Start {
+        timeout: Some(FRAME * 3),
+    });
+    app.update();
+    assert_eq!(status(&app).remaining(), Some(FRAME * 3));
+
+    app.update();
+    app.update();
+    assert_eq!(status(&app).remaining(), Some(FRAME));
+    assert!(events::<DiscoveryFinished>(&app).is_empty());
+
+    app.update();
+    assert_eq!(
+        events::<DiscoveryFinished>(&app),
+        vec![DiscoveryFinished {
+            reason: DiscoveryEnd::TimedOut
+        }]
+    );
+    assert!(!status(&app).is_active());
+}
+
+#[test]
+fn restart_extends_timeout() {
+    let mut app = app();
+    app.world_mut().send_event(GamepadDiscovery::Start {
+        timeout: Some(FRAME * 2),
+    });
+    app.update();
+    app.update();
+
+    app.world_mut().send_event(GamepadDiscovery::Start {
+        timeout: Some(FRAME * 2),
+    });
+    app.update();
+    assert!(events::<DiscoveryStarted>(&app).is_empty());
+    assert_eq!(status(&app).remaining(), Some(FRAME * 2));
+
+    app.update();
+    assert!(status(&app).is_active());
+}
+
+#[test]
+fn stop_without_discovery() {
+    let mut app = app();
+    app.world_mut().send_event(GamepadDiscovery::Stop);
+    app.update();
+
+    assert!(events::<DiscoveryFinished>(&app).is_empty());
+}
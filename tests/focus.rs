@@ -0,0 +1,84 @@
+use bevy_app::prelude::*;
+use bevy_gamepad::{FocusPolicy, GamepadPluginSettings, VirtualGamepads};
+use bevy_input::gamepad::GamepadButton;
+use bevy_window::Window;
+
+mod common;
+
+fn app(focus_policy: FocusPolicy) -> (App, VirtualGamepads) {
+    let (mut app, gamepads) = common::app(GamepadPluginSettings {
+        focus_policy,
+        ..Default::default()
+    });
+    app.world_mut().spawn(Window::default());
+
+    common::connect(&gamepads, 0).unwrap();
+    app.update();
+
+    (app, gamepads)
+}
+
+fn button(gamepads: &VirtualGamepads, button: GamepadButton, value: f32) {
+    common::button(gamepads, 0, button, value).unwrap();
+}
+
+fn focus(app: &mut App, focused: bool) {
+    let mut windows = app.world_mut().query::<&mut Window>();
+    for mut window in windows.iter_mut(app.world_mut()) {
+        window.focused = focused;
+    }
+}
+
+fn pressed(app: &mut App, button: GamepadButton) -> bool {
+    common::state(app).pressed(button)
+}
+
+#[test]
+fn neutralize() {
+    let (mut app, gamepads) = app(FocusPolicy::Neutralize);
+    button(&gamepads, GamepadButton::South, 1.0);
+    app.update();
+    assert!(pressed(&mut app, GamepadButton::South));
+
+    // Held input is released on focus loss, and new input is ignored
+    focus(&mut app, false);
+    app.update();
+    assert!(!pressed(&mut app, GamepadButton::South));
+
+    button(&gamepads, GamepadButton::East, 1.0);
+    app.update();
+    assert!(!pressed(&mut app, GamepadButton::East));
+
+    // The actual state is restored on focus regain
+    button(&gamepads, GamepadButton::South, 0.0);
+    focus(&mut app, true);
+    app.update();
+    assert!(!pressed(&mut app, GamepadButton::South));
+    assert!(pressed(&mut app, GamepadButton::East));
+}
+
+#[test]
+fn ignore() {
+    let (mut app, gamepads) = app(FocusPolicy::Ignore);
+    button(&gamepads, GamepadButton::South, 1.0);
+    app.update();
+
+    focus(&mut app, false);
+    button(&gamepads, GamepadButton::South, 0.0);
+    app.update();
+    assert!(pressed(&mut app, GamepadButton::South));
+
+    focus(&mut app, true);
+    app.update();
+    assert!(!pressed(&mut app, GamepadButton::South));
+}
+
+#[test]
+fn background() {
+    let (mut app, gamepads) = app(FocusPolicy::Background);
+    focus(&mut app, false);
+    button(&gamepads, GamepadButton::South, 1.0);
+    app.update();
+
+    assert!(pressed(&mut app, GamepadButton::South));
+}
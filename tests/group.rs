@@ -0,0 +1,77 @@
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_gamepad::{DisconnectPolicy, GamepadGroup, GamepadPluginSettings, VirtualGamepads};
+use bevy_input::gamepad::{Gamepad, GamepadButton};
+
+mod common;
+
+/// App with two virtual gamepads merged into a group
+fn app() -> (App, VirtualGamepads, [Entity; 2], Entity) {
+    let (mut app, gamepads) = common::app(GamepadPluginSettings {
+        disconnect_policy: DisconnectPolicy::KeepMarked,
+        ..Default::default()
+    });
+    common::connect(&gamepads, 0).unwrap();
+    common::connect(&gamepads, 1).unwrap();
+    app.update();
+
+    let mut members = connected(&mut app);
+    members.sort();
+    let members: [Entity; 2] = members.try_into().unwrap();
+    let group = app.world_mut().spawn(GamepadGroup::new(members)).id();
+    app.update();
+
+    (app, gamepads, members, group)
+}
+
+fn connected(app: &mut App) -> Vec<Entity> {
+    app.world_mut()
+        .query_filtered::<Entity, With<Gamepad>>()
+        .iter(app.world())
+        .collect()
+}
+
+#[test]
+fn reconnect_rejoins_group() {
+    let (mut app, gamepads, [first, _], group) = app();
+    assert_eq!(connected(&mut app), vec![group]);
+
+    gamepads.disconnect(0).unwrap();
+    app.update();
+    common::connect(&gamepads, 0).unwrap();
+    common::button(&gamepads, 0, GamepadButton::South, 1.0).unwrap();
+    app.update();
+
+    // The member stays disconnected and its input reaches the group
+    assert_eq!(connected(&mut app), vec![group]);
+    assert!(app.world().get::<Gamepad>(first).is_none());
+    assert!(
+        app.world()
+            .get::<Gamepad>(group)
+            .unwrap()
+            .pressed(GamepadButton::South)
+    );
+}
+
+#[test]
+fn reconnect_after_every_member_left() {
+    let (mut app, gamepads, _, group) = app();
+
+    gamepads.disconnect(0).unwrap();
+    gamepads.disconnect(1).unwrap();
+    app.update();
+    assert!(connected(&mut app).is_empty());
+
+    // The group is connected again with its first returning member
+    common::connect(&gamepads, 1).unwrap();
+    common::button(&gamepads, 1, GamepadButton::East, 1.0).unwrap();
+    app.update();
+
+    assert_eq!(connected(&mut app), vec![group]);
+    assert!(
+        app.world()
+            .get::<Gamepad>(group)
+            .unwrap()
+            .pressed(GamepadButton::East)
+    );
+}
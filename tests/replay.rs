@@ -0,0 +1,178 @@
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use bevy_app::prelude::*;
+use bevy_color::Srgba;
+use bevy_ecs::prelude::*;
+use bevy_gamepad::{
+    ButtonLabel, ButtonLabels, FaceLayout, GamepadDeviceInfo, GamepadError, GamepadErrorEvent,
+    GamepadGlyphSet, GamepadPlugin, GamepadRecorder, GamepadReplay, VirtualGamepads,
+};
+use bevy_input::{
+    InputPlugin,
+    gamepad::{Gamepad, GamepadButton, GamepadConnection, GamepadConnectionEvent},
+};
+
+mod common;
+
+const NAME: &str = "Arcade\tStick\nC:\\Pads";
+const PROFILE: &str = "Arcade\tStick";
+const MESSAGE: &str = "Device\tfailed\nto start";
+
+const LABELS: ButtonLabels = ButtonLabels::new(&[
+    (
+        GamepadButton::South,
+        ButtonLabel::new("Punch\tLight", "hand.raised").with_color(Srgba::rgb(0.9, 0.1, 0.3)),
+    ),
+    (
+        GamepadButton::East,
+        ButtonLabel::new("Kick\\", "figure.kick"),
+    ),
+]);
+
+/// Errors of every kind with fields, recorded after the gamepad connected
+fn recorded_errors() -> Vec<GamepadError> {
+    vec![
+        GamepadError::Platform(MESSAGE.into()),
+        GamepadError::DeviceInit {
+            id: 0,
+            reason: MESSAGE.into(),
+        },
+        GamepadError::UnsupportedController {
+            id: None,
+            name: NAME.into(),
+        },
+        GamepadError::ChannelClosed,
+    ]
+}
+
+/// Recording target which can be read back while the recorder is still in use
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn record() -> Vec<u8> {
+    let buffer = Buffer::default();
+    let gamepads = VirtualGamepads::default();
+    let mut app = common::base_app();
+    app.insert_resource(gamepads.clone());
+    app.insert_resource(GamepadRecorder::new(buffer.clone()).unwrap());
+    app.add_plugins(GamepadPlugin);
+
+    gamepads
+        .connect(
+            0,
+            NAME,
+            GamepadDeviceInfo::new(PROFILE, FaceLayout::Standard),
+            LABELS,
+        )
+        .unwrap();
+    common::button(&gamepads, 0, GamepadButton::South, 1.0).unwrap();
+    for error in recorded_errors() {
+        gamepads.error(error).unwrap();
+    }
+    app.update();
+
+    buffer.0.lock().unwrap().clone()
+}
+
+#[test]
+fn record_and_replay() {
+    let recording = record();
+    let replay = GamepadReplay::read(recording.as_slice()).unwrap();
+    assert_eq!(replay.len(), 6);
+
+    let mut app = common::base_app();
+    app.insert_resource(replay);
+    app.add_plugins(GamepadPlugin);
+
+    let mut names = Vec::new();
+    let mut errors = Vec::new();
+    for _ in 0..3 {
+        app.update();
+        let world = app.world();
+        names.extend(
+            world
+                .resource::<Events<GamepadConnectionEvent>>()
+                .iter_current_update_events()
+                .filter_map(|event| match &event.connection {
+                    GamepadConnection::Connected { name, .. } => Some(name.clone()),
+                    GamepadConnection::Disconnected => None,
+                }),
+        );
+        errors.extend(
+            world
+                .resource::<Events<GamepadErrorEvent>>()
+                .iter_current_update_events()
+                .cloned(),
+        );
+    }
+    assert_eq!(names, vec![NAME.to_string()]);
+    assert_eq!(
+        errors.iter().map(|event| &event.error).collect::<Vec<_>>(),
+        recorded_errors().iter().collect::<Vec<_>>()
+    );
+
+    let (entity, gamepad, info, glyphs) = app
+        .world_mut()
+        .query::<(Entity, &Gamepad, &GamepadDeviceInfo, &GamepadGlyphSet)>()
+        .single(app.world());
+    // The error about the device is sent for its gamepad again
+    let gamepads: Vec<_> = errors.iter().map(|event| event.gamepad).collect();
+    assert_eq!(gamepads, vec![None, Some(entity), None, None]);
+    assert!(gamepad.pressed(GamepadButton::South));
+    assert_eq!(info.profile(), PROFILE);
+    assert_eq!(glyphs.labels(), LABELS);
+}
+
+#[test]
+fn replayed_labels_interned() {
+    let recording = record();
+    let replay = || {
+        let mut app = common::base_app();
+        app.insert_resource(GamepadReplay::read(recording.as_slice()).unwrap());
+        app.add_plugins(GamepadPlugin);
+        app.update();
+        app.update();
+        app.world_mut()
+            .query::<&GamepadGlyphSet>()
+            .single(app.world())
+            .labels()
+    };
+
+    // Reading the recording again reuses the labels of the first read
+    let (first, second) = (replay(), replay());
+    assert_eq!(first, LABELS);
+    assert!(std::ptr::eq(
+        first.get(GamepadButton::South).unwrap(),
+        second.get(GamepadButton::South).unwrap()
+    ));
+}
+
+#[test]
+fn replay_without_time_plugin() {
+    let mut app = App::new();
+    app.add_plugins(InputPlugin);
+    app.insert_resource(GamepadReplay::read(record().as_slice()).unwrap());
+    app.add_plugins(GamepadPlugin);
+    app.update();
+
+    let errors: Vec<_> = app
+        .world()
+        .resource::<Events<GamepadErrorEvent>>()
+        .iter_current_update_events()
+        .map(|event| event.error.clone())
+        .collect();
+    assert!(matches!(errors.as_slice(), [GamepadError::Platform(_)]));
+}
@@ -0,0 +1,78 @@
+use bevy_app::prelude::*;
+use bevy_gamepad::{
+    GamepadChange, GamepadChannelStats, GamepadPluginSettings, GamepadResync, VirtualGamepads,
+};
+use bevy_input::gamepad::{GamepadAxis, GamepadButton};
+
+mod common;
+
+use common::{gamepad, state};
+
+fn app() -> (App, VirtualGamepads) {
+    let (mut app, gamepads) = common::app(GamepadPluginSettings {
+        channel_capacity: Some(2),
+        ..Default::default()
+    });
+
+    common::connect(&gamepads, 0).unwrap();
+    common::button(&gamepads, 0, GamepadButton::South, 1.0).unwrap();
+    app.update();
+
+    (app, gamepads)
+}
+
+/// Fill the channel with stick input, so the release of the button is dropped
+fn drop_release(app: &mut App, gamepads: &VirtualGamepads) {
+    for value in [0.5, 1.0] {
+        gamepads
+            .input(
+                0,
+                GamepadChange::SingleAxis {
+                    axis: GamepadAxis::LeftStickX,
+                    value,
+                },
+            )
+            .unwrap();
+    }
+    common::button(gamepads, 0, GamepadButton::South, 0.0).unwrap();
+    app.update();
+
+    assert_eq!(app.world().resource::<GamepadChannelStats>().dropped(), 1);
+}
+
+#[test]
+fn resync_all() {
+    let (mut app, gamepads) = app();
+    drop_release(&mut app, &gamepads);
+    assert!(state(&mut app).pressed(GamepadButton::South));
+
+    app.world_mut().send_event(GamepadResync::all());
+    app.update();
+
+    assert!(!state(&mut app).pressed(GamepadButton::South));
+    assert_eq!(state(&mut app).get(GamepadAxis::LeftStickX), Some(1.0));
+}
+
+#[test]
+fn resync_gamepad() {
+    let (mut app, gamepads) = app();
+    drop_release(&mut app, &gamepads);
+
+    let entity = gamepad(&mut app);
+    app.world_mut().send_event(GamepadResync::gamepad(entity));
+    app.update();
+
+    assert!(!state(&mut app).pressed(GamepadButton::South));
+}
+
+#[test]
+fn resync_other_gamepad() {
+    let (mut app, gamepads) = app();
+    drop_release(&mut app, &gamepads);
+
+    let other = app.world_mut().spawn_empty().id();
+    app.world_mut().send_event(GamepadResync::gamepad(other));
+    app.update();
+
+    assert!(state(&mut app).pressed(GamepadButton::South));
+}
@@ -0,0 +1,93 @@
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_gamepad::{
+    DisconnectPolicy, Disconnected, GamepadPluginSettings, GamepadSplit, VirtualGamepads,
+};
+use bevy_input::gamepad::{Gamepad, GamepadButton};
+
+mod common;
+
+fn app(disconnect_policy: DisconnectPolicy) -> (App, VirtualGamepads, Entity) {
+    let (mut app, gamepads) = common::connected_app(GamepadPluginSettings {
+        disconnect_policy,
+        ..Default::default()
+    });
+
+    let gamepad = common::gamepad(&mut app);
+    app.world_mut()
+        .entity_mut(gamepad)
+        .insert(GamepadSplit::left_right());
+    app.update();
+
+    (app, gamepads, gamepad)
+}
+
+fn connected(app: &mut App) -> Vec<Entity> {
+    app.world_mut()
+        .query_filtered::<Entity, With<Gamepad>>()
+        .iter(app.world())
+        .collect()
+}
+
+#[test]
+fn parts_disconnect_with_device() {
+    let (mut app, gamepads, gamepad) = app(DisconnectPolicy::KeepMarked);
+    let parts = connected(&mut app);
+    assert_eq!(parts.len(), 2);
+    assert!(!parts.contains(&gamepad));
+
+    gamepads.disconnect(0).unwrap();
+    app.update();
+
+    assert!(connected(&mut app).is_empty());
+    for part in &parts {
+        assert!(app.world().get::<Disconnected>(*part).is_some());
+    }
+}
+
+#[test]
+fn reconnect_restores_parts() {
+    let (mut app, gamepads, gamepad) = app(DisconnectPolicy::KeepMarked);
+    let mut parts = connected(&mut app);
+    parts.sort();
+
+    gamepads.disconnect(0).unwrap();
+    app.update();
+    common::connect(&gamepads, 0).unwrap();
+    common::button(&gamepads, 0, GamepadButton::South, 1.0).unwrap();
+    app.update();
+
+    let mut reconnected = connected(&mut app);
+    reconnected.sort();
+    assert_eq!(reconnected, parts);
+    assert!(app.world().get::<Gamepad>(gamepad).is_none());
+    for part in &parts {
+        assert!(app.world().get::<Disconnected>(*part).is_none());
+    }
+
+    // Face buttons are routed to the right part
+    let pressed = parts
+        .iter()
+        .filter(|part| {
+            app.world()
+                .get::<Gamepad>(**part)
+                .unwrap()
+                .pressed(GamepadButton::South)
+        })
+        .count();
+    assert_eq!(pressed, 1);
+}
+
+#[test]
+fn parts_despawn_with_device() {
+    let (mut app, gamepads, gamepad) = app(DisconnectPolicy::Despawn);
+    let parts = connected(&mut app);
+
+    gamepads.disconnect(0).unwrap();
+    app.update();
+    app.update();
+
+    for entity in parts.into_iter().chain([gamepad]) {
+        assert!(app.world().get_entity(entity).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_gamepad::GamepadPluginSystems;
+use bevy_input::{
+    InputSystem,
+    gamepad::{Gamepad, GamepadButton, RawGamepadEvent},
+};
+
+mod common;
+
+#[derive(Resource, Default)]
+struct Seen {
+    raw: usize,
+    pressed: bool,
+}
+
+fn read_raw_events(mut events: EventReader<RawGamepadEvent>, mut seen: ResMut<Seen>) {
+    seen.raw += events.read().count();
+}
+
+fn read_gamepads(gamepads: Query<&Gamepad>, mut seen: ResMut<Seen>) {
+    seen.pressed = gamepads
+        .iter()
+        .any(|gamepad| gamepad.pressed(GamepadButton::South));
+}
+
+#[test]
+fn input_processed_in_same_frame() {
+    let (mut app, gamepads) = common::app(Default::default());
+    app.init_resource::<Seen>();
+    app.add_systems(
+        PreUpdate,
+        (
+            read_raw_events
+                .after(GamepadPluginSystems::Platform)
+                .before(InputSystem),
+            read_gamepads.after(InputSystem),
+        ),
+    );
+
+    common::connect(&gamepads, 0).unwrap();
+    common::button(&gamepads, 0, GamepadButton::South, 1.0).unwrap();
+    app.update();
+
+    let seen = app.world().resource::<Seen>();
+    assert_eq!(seen.raw, 1);
+    assert!(seen.pressed);
+}
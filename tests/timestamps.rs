@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use bevy_ecs::prelude::*;
+use bevy_gamepad::{
+    ButtonChange, GamepadChange, GamepadPlatformEvent, InputTimestamp, TimestampedGamepadEvent,
+};
+use bevy_input::gamepad::{GamepadButton, RawGamepadEvent};
+
+mod common;
+
+#[test]
+fn stamps_carried_to_events_in_order() {
+    let (mut app, gamepads) = common::connected_app(Default::default());
+
+    let before = InputTimestamp::now();
+    common::button(&gamepads, 0, GamepadButton::South, 1.0).unwrap();
+    gamepads
+        .send(GamepadPlatformEvent::InputChanged {
+            id: 0,
+            change: GamepadChange::Button(ButtonChange::new(GamepadButton::East, 1.0)),
+            timestamp: InputTimestamp::now().with_device(Duration::from_secs(42)),
+        })
+        .unwrap();
+    common::button(&gamepads, 0, GamepadButton::South, 0.0).unwrap();
+    app.update();
+    let after = InputTimestamp::now();
+
+    let events: Vec<_> = app
+        .world()
+        .resource::<Events<TimestampedGamepadEvent>>()
+        .iter_current_update_events()
+        .cloned()
+        .collect();
+
+    let inputs: Vec<_> = events
+        .iter()
+        .map(|event| match &event.event {
+            RawGamepadEvent::Button(change) => (change.button, change.value),
+            other => panic!("unexpected event {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        inputs,
+        vec![
+            (GamepadButton::South, 1.0),
+            (GamepadButton::East, 1.0),
+            (GamepadButton::South, 0.0),
+        ]
+    );
+
+    // Capture times are monotonic, and taken when the input was sent rather than when it was emitted
+    let captured: Vec<_> = events
+        .iter()
+        .map(|event| event.timestamp.captured())
+        .collect();
+    assert!(captured.is_sorted());
+    assert!(
+        captured
+            .iter()
+            .all(|time| before.captured() <= *time && *time <= after.captured())
+    );
+
+    let device: Vec<_> = events
+        .iter()
+        .map(|event| event.timestamp.device())
+        .collect();
+    assert_eq!(device, vec![None, Some(Duration::from_secs(42)), None]);
+}
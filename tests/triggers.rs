@@ -0,0 +1,101 @@
+use bevy_gamepad::{GamepadChange, GamepadPluginSettings, TriggerReporting};
+use bevy_input::gamepad::{GamepadAxis, GamepadButton, GamepadInput};
+
+mod common;
+
+const LEFT_BUTTON: GamepadInput = GamepadInput::Button(GamepadButton::LeftTrigger2);
+const LEFT_AXIS: GamepadInput = GamepadInput::Axis(GamepadAxis::LeftZ);
+const RIGHT_BUTTON: GamepadInput = GamepadInput::Button(GamepadButton::RightTrigger2);
+const RIGHT_AXIS: GamepadInput = GamepadInput::Axis(GamepadAxis::RightZ);
+
+/// Inputs reported for the button and the axis of both triggers, whichever a profile maps them to
+fn reported(triggers: TriggerReporting) -> Vec<Vec<(GamepadInput, f32)>> {
+    [LEFT_BUTTON, LEFT_AXIS, RIGHT_BUTTON, RIGHT_AXIS]
+        .into_iter()
+        .map(|input| triggers.apply(input, 0.5).collect())
+        .collect()
+}
+
+#[test]
+fn trigger_as_button() {
+    assert_eq!(
+        reported(TriggerReporting::Button),
+        vec![
+            vec![(LEFT_BUTTON, 0.5)],
+            vec![(LEFT_BUTTON, 0.5)],
+            vec![(RIGHT_BUTTON, 0.5)],
+            vec![(RIGHT_BUTTON, 0.5)],
+        ]
+    );
+}
+
+#[test]
+fn trigger_as_axis() {
+    assert_eq!(
+        reported(TriggerReporting::Axis),
+        vec![
+            vec![(LEFT_AXIS, 0.5)],
+            vec![(LEFT_AXIS, 0.5)],
+            vec![(RIGHT_AXIS, 0.5)],
+            vec![(RIGHT_AXIS, 0.5)],
+        ]
+    );
+}
+
+#[test]
+fn trigger_as_both() {
+    let left = vec![(LEFT_BUTTON, 0.5), (LEFT_AXIS, 0.5)];
+    let right = vec![(RIGHT_BUTTON, 0.5), (RIGHT_AXIS, 0.5)];
+    assert_eq!(
+        reported(TriggerReporting::Both),
+        vec![left.clone(), left, right.clone(), right]
+    );
+}
+
+#[test]
+fn other_inputs_unchanged() {
+    for input in [
+        GamepadInput::Button(GamepadButton::South),
+        GamepadInput::Axis(GamepadAxis::LeftStickX),
+    ] {
+        let reported: Vec<_> = TriggerReporting::Both.apply(input, 0.5).collect();
+        assert_eq!(reported, vec![(input, 0.5)]);
+    }
+}
+
+#[test]
+fn trigger_reporting_applied_to_input() {
+    let (mut app, gamepads) = common::connected_app(GamepadPluginSettings {
+        triggers: TriggerReporting::Axis,
+        ..Default::default()
+    });
+    common::button(&gamepads, 0, GamepadButton::LeftTrigger2, 0.5).unwrap();
+    app.update();
+
+    // Bevy reads inputs which were never reported as released
+    let gamepad = common::state(&mut app);
+    assert_eq!(gamepad.get(GamepadAxis::LeftZ), Some(0.5));
+    assert_eq!(
+        gamepad.get(GamepadButton::LeftTrigger2).unwrap_or_default(),
+        0.0
+    );
+}
+
+#[test]
+fn trigger_axis_as_button() {
+    let (mut app, gamepads) = common::connected_app(Default::default());
+    gamepads
+        .input(
+            0,
+            GamepadChange::SingleAxis {
+                axis: GamepadAxis::RightZ,
+                value: 1.0,
+            },
+        )
+        .unwrap();
+    app.update();
+
+    let gamepad = common::state(&mut app);
+    assert_eq!(gamepad.get(GamepadButton::RightTrigger2), Some(1.0));
+    assert_eq!(gamepad.get(GamepadAxis::RightZ).unwrap_or_default(), 0.0);
+}
@@ -0,0 +1,283 @@
+use std::time::Duration;
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_gamepad::{
+    DisconnectPolicy, GamepadChannelStats, GamepadError, GamepadErrorEvent, GamepadPluginSettings,
+    VirtualGamepads,
+};
+use bevy_input::{
+    InputSystem,
+    gamepad::{Gamepad, GamepadButton, GamepadConnectionEvent},
+};
+
+mod common;
+
+use common::{app_with as app, connect};
+
+fn press(gamepads: &VirtualGamepads, id: usize) -> Result<(), GamepadError> {
+    common::button(gamepads, id, GamepadButton::South, 1.0)
+}
+
+fn south_pressed(app: &mut App) -> Vec<bool> {
+    app.world_mut()
+        .query::<&Gamepad>()
+        .iter(app.world())
+        .map(|gamepad| gamepad.pressed(GamepadButton::South))
+        .collect()
+}
+
+fn errors(app: &App) -> Vec<GamepadErrorEvent> {
+    app.world()
+        .resource::<Events<GamepadErrorEvent>>()
+        .iter_current_update_events()
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn connect_and_press() {
+    let gamepads = VirtualGamepads::default();
+    let mut app = app(&gamepads, GamepadPluginSettings::default());
+
+    connect(&gamepads, 0).unwrap();
+    press(&gamepads, 0).unwrap();
+    app.update();
+    app.update();
+
+    assert_eq!(south_pressed(&mut app), vec![true]);
+}
+
+#[test]
+fn input_of_unknown_device_is_delivered_after_connect() {
+    let gamepads = VirtualGamepads::default();
+    let mut app = app(&gamepads, GamepadPluginSettings::default());
+
+    press(&gamepads, 3).unwrap();
+    app.update();
+    assert!(south_pressed(&mut app).is_empty());
+
+    connect(&gamepads, 3).unwrap();
+    app.update();
+    app.update();
+
+    assert_eq!(south_pressed(&mut app), vec![true]);
+    assert_eq!(app.world().resource::<GamepadChannelStats>().orphaned(), 0);
+}
+
+#[test]
+fn input_of_unknown_device_expires() {
+    let gamepads = VirtualGamepads::default();
+    let mut app = app(
+        &gamepads,
+        GamepadPluginSettings {
+            orphan_timeout: Duration::ZERO,
+            ..Default::default()
+        },
+    );
+
+    press(&gamepads, 9).unwrap();
+    app.update();
+    std::thread::sleep(Duration::from_millis(1));
+    app.update();
+
+    assert!(south_pressed(&mut app).is_empty());
+    assert_eq!(app.world().resource::<GamepadChannelStats>().orphaned(), 1);
+}
+
+#[test]
+fn input_after_disconnect_is_not_replayed() {
+    let gamepads = VirtualGamepads::default();
+    let mut app = app(&gamepads, GamepadPluginSettings::default());
+
+    connect(&gamepads, 0).unwrap();
+    app.update();
+    gamepads.disconnect(0).unwrap();
+    press(&gamepads, 0).unwrap();
+    app.update();
+
+    connect(&gamepads, 0).unwrap();
+    app.update();
+
+    assert_eq!(south_pressed(&mut app), vec![false]);
+    assert_eq!(app.world().resource::<GamepadChannelStats>().orphaned(), 1);
+}
+
+/// Whether the gamepad of each disconnection event still existed when bevy_input handled it
+#[derive(Resource, Default)]
+struct DisconnectedExists(Vec<bool>);
+
+#[test]
+fn despawn_after_disconnect_is_handled() {
+    let gamepads = VirtualGamepads::default();
+    let mut app = app(
+        &gamepads,
+        GamepadPluginSettings {
+            disconnect_policy: DisconnectPolicy::Despawn,
+            ..Default::default()
+        },
+    );
+    app.init_resource::<DisconnectedExists>();
+    app.add_systems(
+        PreUpdate,
+        (|mut events: EventReader<GamepadConnectionEvent>,
+          entities: Query<()>,
+          mut exists: ResMut<DisconnectedExists>| {
+            for event in events.read().filter(|event| event.disconnected()) {
+                exists.0.push(entities.contains(event.gamepad));
+            }
+        })
+        .after(InputSystem),
+    );
+
+    connect(&gamepads, 0).unwrap();
+    app.update();
+    let gamepad = app
+        .world_mut()
+        .query_filtered::<Entity, With<Gamepad>>()
+        .single(app.world());
+
+    gamepads.disconnect(0).unwrap();
+    app.update();
+
+    assert_eq!(app.world().resource::<DisconnectedExists>().0, vec![true]);
+    assert!(app.world().get_entity(gamepad).is_err());
+}
+
+#[test]
+fn disconnect_of_unknown_device() {
+    let gamepads = VirtualGamepads::default();
+    let mut app = app(&gamepads, GamepadPluginSettings::default());
+
+    gamepads.disconnect(7).unwrap();
+    app.update();
+
+    connect(&gamepads, 7).unwrap();
+    gamepads.disconnect(7).unwrap();
+    gamepads.disconnect(7).unwrap();
+    app.update();
+
+    assert!(errors(&app).is_empty());
+}
+
+#[test]
+fn closed_channel() {
+    let gamepads = VirtualGamepads::default();
+    assert_eq!(press(&gamepads, 0), Err(GamepadError::ChannelClosed));
+
+    let mut app = app(&gamepads, GamepadPluginSettings::default());
+    connect(&gamepads, 0).unwrap();
+    app.update();
+    drop(app);
+
+    assert_eq!(press(&gamepads, 0), Err(GamepadError::ChannelClosed));
+    assert_eq!(gamepads.disconnect(0), Err(GamepadError::ChannelClosed));
+}
+
+#[test]
+fn full_channel_drops_events() {
+    let gamepads = VirtualGamepads::default();
+    let mut app = app(
+        &gamepads,
+        GamepadPluginSettings {
+            channel_capacity: Some(1),
+            ..Default::default()
+        },
+    );
+
+    connect(&gamepads, 0).unwrap();
+    press(&gamepads, 0).unwrap();
+    press(&gamepads, 0).unwrap();
+    app.update();
+
+    assert_eq!(app.world().resource::<GamepadChannelStats>().dropped(), 1);
+}
+
+#[test]
+fn full_channel_keeps_connections() {
+    let gamepads = VirtualGamepads::default();
+    let mut app = app(
+        &gamepads,
+        GamepadPluginSettings {
+            channel_capacity: Some(1),
+            ..Default::default()
+        },
+    );
+
+    connect(&gamepads, 0).unwrap();
+    press(&gamepads, 0).unwrap();
+    connect(&gamepads, 1).unwrap();
+    press(&gamepads, 1).unwrap();
+    app.update();
+
+    assert_eq!(app.world().resource::<GamepadChannelStats>().dropped(), 1);
+    assert_eq!(south_pressed(&mut app).len(), 2);
+
+    // Input is accepted again once the channel is drained
+    press(&gamepads, 1).unwrap();
+    app.update();
+    assert_eq!(south_pressed(&mut app), vec![true, true]);
+}
+
+#[test]
+fn driver_error_is_sent_as_event() {
+    let gamepads = VirtualGamepads::default();
+    let mut app = app(&gamepads, GamepadPluginSettings::default());
+
+    let error = GamepadError::UnsupportedController {
+        id: None,
+        name: "Virtual Remote".into(),
+    };
+    gamepads.error(error.clone()).unwrap();
+    app.update();
+
+    assert_eq!(
+        errors(&app),
+        vec![GamepadErrorEvent {
+            gamepad: None,
+            error
+        }]
+    );
+}
+
+#[test]
+fn driver_start_failure_is_sent_as_event() {
+    let gamepads = VirtualGamepads::default();
+    let _first = app(&gamepads, GamepadPluginSettings::default());
+
+    // The virtual gamepads are already driven by the first app
+    let mut second = app(&gamepads, GamepadPluginSettings::default());
+    second.update();
+
+    let errors = errors(&second);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0].error, GamepadError::Platform(_)));
+}
+
+#[test]
+fn shutdown_on_exit() {
+    let gamepads = VirtualGamepads::default();
+    let mut app = app(&gamepads, GamepadPluginSettings::default());
+
+    connect(&gamepads, 0).unwrap();
+    app.world_mut().send_event(AppExit::Success);
+    app.update();
+
+    assert_eq!(press(&gamepads, 0), Err(GamepadError::ChannelClosed));
+}
+
+#[test]
+fn shutdown_on_drop() {
+    let gamepads = VirtualGamepads::default();
+    drop(app(&gamepads, GamepadPluginSettings::default()));
+
+    // The driver of the dropped app released the gamepads
+    let mut app = app(&gamepads, GamepadPluginSettings::default());
+    connect(&gamepads, 0).unwrap();
+    press(&gamepads, 0).unwrap();
+    app.update();
+    app.update();
+
+    assert!(errors(&app).is_empty());
+    assert_eq!(south_pressed(&mut app), vec![true]);
+}